@@ -0,0 +1,397 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, ExprUnary, Fields, Ident, Lit, LitStr,
+    Result, UnOp,
+};
+
+use crate::add_bound;
+
+/// The `#[rpc_error(code = .., message = "..")]` attribute of a struct, an enum or a variant.
+#[derive(Clone, Default)]
+struct ErrorAttr {
+    code: Option<Expr>,
+    message: Option<LitStr>,
+    /// The catch-all variant, which wraps the original error.
+    other: bool,
+}
+
+impl ErrorAttr {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut error = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("rpc_error") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("code") {
+                    let code = meta.value()?.parse()?;
+                    check_code(&code)?;
+                    error.code = Some(code);
+                    Ok(())
+                } else if meta.path.is_ident("message") {
+                    error.message = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("other") {
+                    error.other = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `code`, `message` or `other`"))
+                }
+            })?;
+        }
+        Ok(error)
+    }
+
+    /// Takes the missing code and message from the attribute of the enum.
+    fn or(self, default: &Self) -> Self {
+        Self {
+            code: self.code.or_else(|| default.code.clone()),
+            message: self.message.or_else(|| default.message.clone()),
+            other: self.other,
+        }
+    }
+
+    fn code(&self, ident: &Ident) -> Result<&Expr> {
+        self.code
+            .as_ref()
+            .ok_or_else(|| Error::new_spanned(ident, "missing `#[rpc_error(code = ..)]`"))
+    }
+}
+
+/// Checks that a literal code is not reserved, unless it is pre-defined or a server error code,
+/// as `ErrorRegistry::register` does.
+fn check_code(code: &Expr) -> Result<()> {
+    let (negative, lit) = match code {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => (false, lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => (true, lit),
+            _ => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+    let value = lit.base10_parse::<i64>()?;
+    let value = if negative { -value } else { value };
+    let predefined = matches!(value, -32700 | -32600 | -32601 | -32602 | -32603);
+    let server_error = (-32099..=-32000).contains(&value);
+    if (-32768..=-32000).contains(&value) && !predefined && !server_error {
+        return Err(Error::new_spanned(
+            code,
+            format!(
+                "error code {} is reserved, expected a pre-defined code, \
+                 a server error code in -32099..=-32000 or a code outside of -32768..=-32000",
+                value
+            ),
+        ));
+    }
+    Ok(())
+}
+
+struct Field {
+    /// The variable which the field is bound to.
+    binding: Ident,
+    /// The key of the field in the error data.
+    name: String,
+    skip: bool,
+}
+
+/// Binds the fields of a variant, e.g. `{ a, b }` or `(_0, _1)`.
+fn bind_fields(fields: &Fields) -> Result<(TokenStream, Vec<Field>)> {
+    let mut bound = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let mut skip = false;
+        for attr in &field.attrs {
+            if !attr.path().is_ident("rpc_error") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip`"))
+                }
+            })?;
+        }
+        let (binding, name) = match &field.ident {
+            Some(ident) => (
+                ident.clone(),
+                ident.to_string().trim_start_matches("r#").to_owned(),
+            ),
+            None => (format_ident!("_{}", index), index.to_string()),
+        };
+        bound.push(Field {
+            binding,
+            name,
+            skip,
+        });
+    }
+
+    let bindings = bound.iter().map(|field| &field.binding);
+    let pattern = match fields {
+        Fields::Named(_) => quote!({ #(#bindings),* }),
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!(),
+    };
+    Ok((pattern, bound))
+}
+
+/// Serializes the fields which are not skipped into the error data.
+///
+/// A field which can't be serialized is replaced with a string describing the failure.
+fn expand_data(fields: &Fields, bound: &[Field]) -> TokenStream {
+    let data = bound.iter().filter(|field| !field.skip).collect::<Vec<_>>();
+    let bindings = data.iter().map(|field| &field.binding);
+    let value = match (fields, data.len()) {
+        (_, 0) => return quote!(::std::option::Option::None),
+        (Fields::Named(_), _) => {
+            let names = data.iter().map(|field| &field.name);
+            quote! {{
+                let mut map = ::jsonrpc_types::Map::new();
+                #(
+                    map.insert(
+                        ::std::string::String::from(#names),
+                        ::jsonrpc_types::__private::to_data(&#bindings),
+                    );
+                )*
+                ::jsonrpc_types::Value::Object(map)
+            }}
+        }
+        (_, 1) => quote!(::jsonrpc_types::__private::to_data(#(&#bindings)*)),
+        (_, _) => quote!(::jsonrpc_types::__private::to_data((#(&#bindings),*))),
+    };
+    quote!(::std::option::Option::Some(#value))
+}
+
+/// Formats the message, in which `{name}` or `{0}` are replaced with the fields of the variant.
+fn expand_message(message: Option<&LitStr>) -> TokenStream {
+    let message = match message {
+        Some(message) => message,
+        None => return quote!(code.description()),
+    };
+    let value = message.value();
+    if !value.contains('{') {
+        return quote!(::std::string::String::from(#message));
+    }
+
+    // Positional fields are bound to `_0`, `_1`, ..., so that they are captured by name.
+    let mut format = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        format.push(c);
+        if c == '{' {
+            if chars.peek() == Some(&'{') {
+                format.extend(chars.next());
+            } else if chars.peek().is_some_and(char::is_ascii_digit) {
+                format.push('_');
+            }
+        }
+    }
+    let format = LitStr::new(&format, message.span());
+    quote!(::std::format!(#format))
+}
+
+/// Checks that the catch-all variant wraps a single `Error`.
+fn check_other(fields: &Fields, ident: &Ident) -> Result<()> {
+    match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(()),
+        _ => Err(Error::new_spanned(
+            ident,
+            "the `#[rpc_error(other)]` variant must wrap a single `Error`",
+        )),
+    }
+}
+
+fn expand_arm(
+    path: TokenStream,
+    fields: &Fields,
+    error: &ErrorAttr,
+    ident: &Ident,
+) -> Result<TokenStream> {
+    if error.other {
+        check_other(fields, ident)?;
+        return Ok(quote! {
+            #path(error) => ::jsonrpc_types::IntoRpcError::into_rpc_error(error),
+        });
+    }
+    let code = error.code(ident)?;
+    let (pattern, bound) = bind_fields(fields)?;
+    let message = expand_message(error.message.as_ref());
+    let data = expand_data(fields, &bound);
+    Ok(quote! {
+        #path #pattern => {
+            let code = ::jsonrpc_types::ErrorCode::from(#code);
+            let message = #message;
+            ::jsonrpc_types::Error::with_data(code, message, #data)
+        }
+    })
+}
+
+pub fn expand_into_rpc_error(input: &DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let container = ErrorAttr::from_attrs(&input.attrs)?;
+    let arms = match &input.data {
+        Data::Struct(data) => vec![expand_arm(quote!(Self), &data.fields, &container, ident)?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let error = ErrorAttr::from_attrs(&variant.attrs)?.or(&container);
+                let variant_ident = &variant.ident;
+                expand_arm(
+                    quote!(Self::#variant_ident),
+                    &variant.fields,
+                    &error,
+                    variant_ident,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                ident,
+                "rpc errors can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    let generics = add_bound(
+        &input.generics,
+        quote!(::jsonrpc_types::__private::Serialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::jsonrpc_types::IntoRpcError for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn into_rpc_error(self) -> ::jsonrpc_types::Error {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Deserializes the fields which are not skipped from the error data.
+fn expand_from_data(path: TokenStream, fields: &Fields, bound: &[Field]) -> TokenStream {
+    let data = bound.iter().filter(|field| !field.skip).count();
+    let values = bound.iter().map(|field| {
+        let name = &field.name;
+        match (field.skip, fields, data) {
+            (true, _, _) => quote!(::std::default::Default::default()),
+            (false, Fields::Named(_), _) => {
+                quote!(::jsonrpc_types::__private::from_data(map.remove(#name))?)
+            }
+            (false, _, 1) => quote!(::jsonrpc_types::__private::from_data(error.data.clone())?),
+            (false, _, _) => quote!(::jsonrpc_types::__private::from_data(array.next())?),
+        }
+    });
+    let (members, construct) = match fields {
+        Fields::Named(_) if data > 0 => {
+            let bindings = bound.iter().map(|field| &field.binding);
+            (
+                quote! {
+                    let mut map = match error.data.clone() {
+                        ::std::option::Option::Some(::jsonrpc_types::Value::Object(map)) => map,
+                        ::std::option::Option::None => ::jsonrpc_types::Map::new(),
+                        ::std::option::Option::Some(_) => return ::std::option::Option::None,
+                    };
+                },
+                quote!(#path { #(#bindings: #values),* }),
+            )
+        }
+        Fields::Unnamed(_) if data > 1 => (
+            quote! {
+                let mut array = match error.data.clone() {
+                    ::std::option::Option::Some(::jsonrpc_types::Value::Array(array)) => {
+                        array.into_iter()
+                    }
+                    _ => return ::std::option::Option::None,
+                };
+            },
+            quote!(#path(#(#values),*)),
+        ),
+        Fields::Named(_) => {
+            let bindings = bound.iter().map(|field| &field.binding);
+            (quote!(), quote!(#path { #(#bindings: #values),* }))
+        }
+        Fields::Unnamed(_) => (quote!(), quote!(#path(#(#values),*))),
+        Fields::Unit => (quote!(), quote!(#path)),
+    };
+    quote! {
+        #members
+        ::std::option::Option::Some(#construct)
+    }
+}
+
+pub fn expand_from_rpc_error(input: &DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "FromRpcError can only be derived for enums",
+            ))
+        }
+    };
+    let container = ErrorAttr::from_attrs(&input.attrs)?;
+
+    let mut other = None;
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let error = ErrorAttr::from_attrs(&variant.attrs)?.or(&container);
+        let variant_ident = &variant.ident;
+        if error.other {
+            check_other(&variant.fields, variant_ident)?;
+            if other.is_some() {
+                return Err(Error::new_spanned(
+                    variant_ident,
+                    "duplicate `#[rpc_error(other)]` variant",
+                ));
+            }
+            other = Some(variant_ident);
+            continue;
+        }
+        let code = error.code(variant_ident)?;
+        let (_, bound) = bind_fields(&variant.fields)?;
+        let construct = expand_from_data(quote!(Self::#variant_ident), &variant.fields, &bound);
+        arms.push(quote! {
+            if error.code.code() == ::jsonrpc_types::ErrorCode::from(#code).code() {
+                let variant = (|| -> ::std::option::Option<Self> { #construct })();
+                if let ::std::option::Option::Some(variant) = variant {
+                    return variant;
+                }
+            }
+        });
+    }
+    let other = other.ok_or_else(|| {
+        Error::new_spanned(
+            ident,
+            "missing `#[rpc_error(other)]` variant for unknown errors",
+        )
+    })?;
+
+    let generics = add_bound(
+        &input.generics,
+        quote!(::jsonrpc_types::__private::DeserializeOwned),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::jsonrpc_types::FromRpcError for #ident #ty_generics #where_clause {
+            fn from_rpc_error(error: ::jsonrpc_types::Error) -> Self {
+                #(#arms)*
+                Self::#other(error)
+            }
+        }
+    })
+}
@@ -0,0 +1,91 @@
+//! Derive macros for [jsonrpc-types](https://docs.rs/jsonrpc-types).
+
+#![deny(unused_imports)]
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+
+mod error;
+mod params;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, parse_quote, DeriveInput, GenericParam, Generics};
+
+/// Derives `ToParams` for a struct.
+///
+/// Named fields are converted into parameters by-name, unless the struct is annotated with
+/// `#[params(positional)]`. Tuple struct fields are always converted by-position.
+/// A field can be renamed with `#[params(rename = "name")]`.
+#[proc_macro_derive(ToParams, attributes(params))]
+pub fn derive_to_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    params::expand_to_params(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromParams` for a struct.
+///
+/// Fields are parsed from parameters either by-position, in declaration order, or by-name.
+/// Missing trailing positional parameters are parsed as omitted fields, e.g. `None` for `Option`.
+/// A field can be renamed with `#[params(rename = "name")]`.
+#[proc_macro_derive(FromParams, attributes(params))]
+pub fn derive_from_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    params::expand_from_params(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `IntoRpcError` for an enum or a struct.
+///
+/// Each variant is converted into an error object with the code and the message of its
+/// `#[rpc_error(code = -32001, message = "...")]` attribute, or of the attribute of the enum.
+/// The message may refer to the fields of the variant, e.g. `{name}` or `{0}`, and defaults to
+/// the description of the code. The fields are serialized into the `data` member, unless they
+/// are annotated with `#[rpc_error(skip)]`. The variant annotated with `#[rpc_error(other)]`
+/// wraps an `Error`, which is returned as is.
+///
+/// A field which can't be serialized into JSON is replaced with a string describing the failure.
+/// Codes of the reserved range -32768..=-32000 are rejected, except the pre-defined codes and
+/// the server error codes -32099..=-32000:
+///
+/// ```compile_fail
+/// use jsonrpc_types::IntoRpcError;
+///
+/// #[derive(IntoRpcError)]
+/// #[rpc_error(code = -32500)]
+/// struct Reserved;
+/// ```
+#[proc_macro_derive(IntoRpcError, attributes(rpc_error))]
+pub fn derive_into_rpc_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    error::expand_into_rpc_error(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromRpcError` for an enum.
+///
+/// An error is converted into the first variant whose `#[rpc_error(code = ..)]` matches the code
+/// of the error, and whose fields can be deserialized from the `data` member, mirroring
+/// `IntoRpcError`. Fields annotated with `#[rpc_error(skip)]` are set to their default value.
+/// Any other error is wrapped into the variant annotated with `#[rpc_error(other)]`.
+#[proc_macro_derive(FromRpcError, attributes(rpc_error))]
+pub fn derive_from_rpc_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    error::expand_from_rpc_error(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Adds `bound` to every type parameter of the item.
+fn add_bound(generics: &Generics, bound: proc_macro2::TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
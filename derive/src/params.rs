@@ -0,0 +1,207 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Member, Result, Type};
+
+use crate::add_bound;
+
+/// The way struct fields are mapped onto request parameters.
+#[derive(Clone, Copy, PartialEq)]
+enum Style {
+    /// By-name, through an Object.
+    Named,
+    /// By-position, through an Array.
+    Positional,
+}
+
+struct Field {
+    member: Member,
+    name: String,
+    ty: Type,
+}
+
+struct Container {
+    style: Style,
+    fields: Vec<Field>,
+}
+
+impl Container {
+    fn from_input(input: &DeriveInput) -> Result<Self> {
+        let data = match &input.data {
+            Data::Struct(data) => data,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "params can only be derived for structs",
+                ))
+            }
+        };
+
+        let mut style = None;
+        for attr in &input.attrs {
+            if !attr.path().is_ident("params") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("positional") {
+                    style = Some(Style::Positional);
+                    Ok(())
+                } else if meta.path.is_ident("named") {
+                    style = Some(Style::Named);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `positional` or `named`"))
+                }
+            })?;
+        }
+
+        let style = match (&data.fields, style) {
+            (Fields::Unnamed(_), Some(Style::Named)) => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "tuple structs can only be converted by-position",
+                ))
+            }
+            (Fields::Unnamed(_), _) => Style::Positional,
+            (_, style) => style.unwrap_or(Style::Named),
+        };
+
+        let mut fields = Vec::new();
+        for (index, field) in data.fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            let mut name = match &field.ident {
+                Some(ident) => ident.to_string().trim_start_matches("r#").to_owned(),
+                None => index.to_string(),
+            };
+            for attr in &field.attrs {
+                if !attr.path().is_ident("params") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `rename`"))
+                    }
+                })?;
+            }
+            fields.push(Field {
+                member,
+                name,
+                ty: field.ty.clone(),
+            });
+        }
+
+        Ok(Self { style, fields })
+    }
+}
+
+pub fn expand_to_params(input: &DeriveInput) -> Result<TokenStream> {
+    let container = Container::from_input(input)?;
+    let ident = &input.ident;
+    let generics = add_bound(
+        &input.generics,
+        quote!(::jsonrpc_types::__private::Serialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let members = container.fields.iter().map(|field| &field.member);
+    let body = if container.fields.is_empty() {
+        quote!(::std::result::Result::Ok(::std::option::Option::None))
+    } else {
+        match container.style {
+            Style::Named => {
+                let names = container.fields.iter().map(|field| &field.name);
+                quote! {
+                    let mut map = ::jsonrpc_types::Map::new();
+                    #(
+                        map.insert(
+                            ::std::string::String::from(#names),
+                            ::jsonrpc_types::__private::to_value(self.#members)?,
+                        );
+                    )*
+                    ::std::result::Result::Ok(::std::option::Option::Some(
+                        ::jsonrpc_types::Params::Map(map),
+                    ))
+                }
+            }
+            Style::Positional => quote! {
+                ::std::result::Result::Ok(::std::option::Option::Some(
+                    ::jsonrpc_types::Params::Array(::std::vec![
+                        #(::jsonrpc_types::__private::to_value(self.#members)?),*
+                    ]),
+                ))
+            },
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::jsonrpc_types::ToParams for #ident #ty_generics #where_clause {
+            fn try_to_params(
+                self,
+            ) -> ::std::result::Result<
+                ::std::option::Option<::jsonrpc_types::Params>,
+                ::jsonrpc_types::__private::JsonError,
+            > {
+                #body
+            }
+        }
+    })
+}
+
+pub fn expand_from_params(input: &DeriveInput) -> Result<TokenStream> {
+    let container = Container::from_input(input)?;
+    let ident = &input.ident;
+    let generics = add_bound(
+        &input.generics,
+        quote!(::jsonrpc_types::__private::DeserializeOwned),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let members = container.fields.iter().map(|field| &field.member);
+    let names = container.fields.iter().map(|field| &field.name);
+    let tys = container.fields.iter().map(|field| &field.ty);
+    let len = container.fields.len();
+    let body = match container.style {
+        Style::Named => {
+            let keys = names.clone();
+            quote! {
+                let mut map = params.unwrap_or_default().into_named(&[#(#keys),*])?;
+                ::std::result::Result::Ok(Self {
+                    #(
+                        #members: ::jsonrpc_types::__private::from_value::<#tys>(map.remove(#names), #names)?,
+                    )*
+                })
+            }
+        }
+        Style::Positional => {
+            let keys = names.clone();
+            quote! {
+                let mut array = params.unwrap_or_default().into_positional(&[#(#keys),*])?.into_iter();
+                if array.len() > #len {
+                    return ::std::result::Result::Err(::jsonrpc_types::Error::invalid_params(
+                        ::std::format!("invalid length {}, expected at most {} parameters", array.len(), #len),
+                    ));
+                }
+                ::std::result::Result::Ok(Self {
+                    #(
+                        #members: ::jsonrpc_types::__private::from_value::<#tys>(array.next(), #names)?,
+                    )*
+                })
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::jsonrpc_types::FromParams for #ident #ty_generics #where_clause {
+            fn from_params(
+                params: ::std::option::Option<::jsonrpc_types::Params>,
+            ) -> ::std::result::Result<Self, ::jsonrpc_types::Error> {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,135 @@
+use jsonrpc_types::{Error, ErrorCode, FromRpcError, IntoRpcError, Output, Value};
+
+#[derive(Debug, PartialEq, IntoRpcError, FromRpcError)]
+#[rpc_error(code = -32000)]
+enum AppError {
+    #[rpc_error(code = -32001, message = "Unknown block {0}")]
+    UnknownBlock(u64),
+    #[rpc_error(code = 4001, message = "Insufficient funds: {balance} < {amount}")]
+    InsufficientFunds {
+        balance: u64,
+        amount: u64,
+        #[rpc_error(skip)]
+        account: String,
+    },
+    #[rpc_error(message = "Node is syncing")]
+    Syncing,
+    #[rpc_error(code = -32002)]
+    Other(String, u64),
+    #[rpc_error(other)]
+    Unknown(Error),
+}
+
+#[derive(Debug, IntoRpcError)]
+#[rpc_error(code = -32602)]
+struct InvalidField<T> {
+    field: T,
+}
+
+#[test]
+fn enum_into_rpc_error() {
+    assert_eq!(
+        AppError::UnknownBlock(7).into_rpc_error(),
+        Error::with_data(
+            ErrorCode::ServerError(-32001),
+            "Unknown block 7",
+            Some(Value::from(7))
+        )
+    );
+
+    let error = AppError::InsufficientFunds {
+        balance: 1,
+        amount: 2,
+        account: "alice".into(),
+    };
+    assert_eq!(
+        serde_json::to_string(&error.into_rpc_error()).unwrap(),
+        r#"{"code":4001,"message":"Insufficient funds: 1 < 2","data":{"amount":2,"balance":1}}"#
+    );
+
+    assert_eq!(
+        serde_json::to_string(&AppError::Syncing.into_rpc_error()).unwrap(),
+        r#"{"code":-32000,"message":"Node is syncing"}"#
+    );
+
+    assert_eq!(
+        serde_json::to_string(&AppError::Other("foo".into(), 1).into_rpc_error()).unwrap(),
+        r#"{"code":-32002,"message":"Server error","data":["foo",1]}"#
+    );
+
+    let error = AppError::Unknown(Error::method_not_found());
+    assert_eq!(error.into_rpc_error(), Error::method_not_found());
+}
+
+#[test]
+fn enum_from_rpc_error() {
+    let cases = vec![
+        (AppError::UnknownBlock(7), AppError::UnknownBlock(7)),
+        (
+            AppError::InsufficientFunds {
+                balance: 1,
+                amount: 2,
+                account: "alice".into(),
+            },
+            // Skipped fields are not sent.
+            AppError::InsufficientFunds {
+                balance: 1,
+                amount: 2,
+                account: String::new(),
+            },
+        ),
+        (AppError::Syncing, AppError::Syncing),
+        (
+            AppError::Other("foo".into(), 1),
+            AppError::Other("foo".into(), 1),
+        ),
+        (
+            AppError::Unknown(Error::method_not_found()),
+            AppError::Unknown(Error::method_not_found()),
+        ),
+    ];
+    for (error, expect) in cases {
+        let error = serde_json::to_string(&error.into_rpc_error()).unwrap();
+        let error = serde_json::from_str::<Error>(&error).unwrap();
+        assert_eq!(AppError::from_rpc_error(error), expect);
+    }
+
+    // The data doesn't match the fields of the variant.
+    let error = Error::with_data(
+        ErrorCode::ServerError(-32001),
+        "Unknown block",
+        Some(Value::from("foo")),
+    );
+    assert_eq!(
+        AppError::from_rpc_error(error.clone()),
+        AppError::Unknown(error)
+    );
+
+    let output = serde_json::from_str::<Output<u64>>(
+        r#"{"jsonrpc":"2.0","error":{"code":-32001,"message":"Unknown block 7","data":7},"id":1}"#,
+    )
+    .unwrap();
+    let result: Result<u64, AppError> = output.into();
+    assert_eq!(result, Err(AppError::UnknownBlock(7)));
+
+    let output = Output::<u64>::success(7, 1.into());
+    let result: Result<u64, AppError> = output.into();
+    assert_eq!(result, Ok(7));
+}
+
+#[test]
+fn struct_into_rpc_error() {
+    let error = InvalidField { field: "foo" }.into_rpc_error();
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"code":-32602,"message":"Invalid params","data":{"field":"foo"}}"#
+    );
+    // A field which can't be serialized is replaced with a description of the failure.
+    let mut field = std::collections::BTreeMap::new();
+    field.insert((1, 2), 3);
+    let error = InvalidField { field }.into_rpc_error();
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"code":-32602,"message":"Invalid params","data":{"field":"unserializable error data: key must be a string"}}"#
+    );
+}
@@ -0,0 +1,154 @@
+use jsonrpc_types::{Error, FromParams, MethodCall, Params, ToParams, Value};
+
+#[derive(Debug, PartialEq, ToParams, FromParams)]
+struct Named {
+    name: String,
+    #[params(rename = "count")]
+    n: u64,
+    flag: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, ToParams, FromParams)]
+#[params(positional)]
+struct Positional {
+    name: String,
+    n: u64,
+}
+
+#[derive(Debug, PartialEq, ToParams, FromParams)]
+struct Tuple(String, u64);
+
+#[derive(Debug, PartialEq, ToParams, FromParams)]
+struct Empty;
+
+#[derive(Debug, PartialEq, ToParams, FromParams)]
+struct Generic<T> {
+    value: T,
+}
+
+#[test]
+fn named_params() {
+    let params = Named {
+        name: "foo".into(),
+        n: 1,
+        flag: None,
+    };
+    let call = MethodCall::new("foo", params, 1.into());
+    assert_eq!(
+        serde_json::to_string(&call).unwrap(),
+        r#"{"jsonrpc":"2.0","method":"foo","params":{"count":1,"flag":null,"name":"foo"},"id":1}"#
+    );
+
+    let params = serde_json::from_str::<Params>(r#"{"name":"foo","count":1}"#).unwrap();
+    assert_eq!(
+        Named::from_params(Some(params)).unwrap(),
+        Named {
+            name: "foo".into(),
+            n: 1,
+            flag: None,
+        }
+    );
+
+    let params = serde_json::from_str::<Params>(r#"{"name":"foo"}"#).unwrap();
+    assert_eq!(
+        Named::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("missing field `count`")
+    );
+
+    // Unknown values are rejected by-name as well as by-position.
+    let params = serde_json::from_str::<Params>(r#"{"name":"foo","count":1,"other":2}"#).unwrap();
+    assert_eq!(
+        Named::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("unknown field `other`")
+    );
+    let params = serde_json::from_str::<Params>(r#"["foo",1,null,2]"#).unwrap();
+    assert_eq!(
+        Named::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("invalid length 4, expected at most 3 parameters")
+    );
+}
+
+#[test]
+fn params_by_position_and_by_name() {
+    let expect = Named {
+        name: "foo".into(),
+        n: 1,
+        flag: None,
+    };
+    let params = serde_json::from_str::<Params>(r#"["foo",1]"#).unwrap();
+    assert_eq!(Named::from_params(Some(params)).unwrap(), expect);
+    let params = serde_json::from_str::<Params>(r#"["foo",1,null]"#).unwrap();
+    assert_eq!(Named::from_params(Some(params)).unwrap(), expect);
+    let params = serde_json::from_str::<Params>(r#"["foo"]"#).unwrap();
+    assert_eq!(
+        Named::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("missing field `count`")
+    );
+
+    let expect = Positional {
+        name: "foo".into(),
+        n: 1,
+    };
+    let params = serde_json::from_str::<Params>(r#"{"n":1,"name":"foo"}"#).unwrap();
+    assert_eq!(Positional::from_params(Some(params)).unwrap(), expect);
+    let params = serde_json::from_str::<Params>(r#"{"n":1,"name":"foo","other":2}"#).unwrap();
+    assert_eq!(
+        Positional::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("unknown field `other`")
+    );
+    let params = serde_json::from_str::<Params>(r#"["foo",1,2]"#).unwrap();
+    assert_eq!(
+        Positional::from_params(Some(params)).unwrap_err(),
+        Error::invalid_params("invalid length 3, expected at most 2 parameters")
+    );
+}
+
+#[test]
+fn positional_params() {
+    let params = Positional {
+        name: "foo".into(),
+        n: 1,
+    };
+    assert_eq!(
+        params.to_params(),
+        Some(Params::Array(vec![Value::from("foo"), Value::from(1)]))
+    );
+    let params = serde_json::from_str::<Params>(r#"["foo",1]"#).unwrap();
+    assert_eq!(
+        Positional::from_params(Some(params)).unwrap(),
+        Positional {
+            name: "foo".into(),
+            n: 1,
+        }
+    );
+    let params = serde_json::from_str::<Params>(r#"["foo","bar"]"#).unwrap();
+    assert!(Positional::from_params(Some(params)).is_err());
+
+    let params = Tuple("foo".into(), 1);
+    assert_eq!(
+        params.to_params(),
+        Some(Params::Array(vec![Value::from("foo"), Value::from(1)]))
+    );
+    let params = serde_json::from_str::<Params>(r#"["foo",1]"#).unwrap();
+    assert_eq!(
+        Tuple::from_params(Some(params)).unwrap(),
+        Tuple("foo".into(), 1)
+    );
+}
+
+#[test]
+fn empty_and_generic_params() {
+    assert_eq!(Empty.to_params(), None);
+    assert_eq!(Empty::from_params(None).unwrap(), Empty);
+
+    let params = Generic { value: vec![1u8] }.to_params();
+    assert_eq!(
+        Generic::<Vec<u8>>::from_params(params).unwrap(),
+        Generic { value: vec![1u8] }
+    );
+
+    // A map whose keys aren't strings can't be serialized into JSON.
+    let mut value = std::collections::BTreeMap::new();
+    value.insert((1, 2), 3);
+    assert!(Generic { value }.try_to_params().is_err());
+}
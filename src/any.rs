@@ -0,0 +1,481 @@
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use serde::{
+    de::{self, DeserializeOwned},
+    Serialize,
+};
+use serde_json::{value::RawValue, Value};
+
+use crate::{convert::ConversionError, raw, v1, v1_1, v2};
+
+/// The JSON-RPC protocol version of a message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ProtocolVersion {
+    /// JSON-RPC 1.0
+    V1_0,
+    /// JSON-RPC 1.1
+    V1_1,
+    /// JSON-RPC 2.0
+    V2_0,
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1_0 => f.write_str("1.0"),
+            Self::V1_1 => f.write_str("1.1"),
+            Self::V2_0 => f.write_str("2.0"),
+        }
+    }
+}
+
+/// The protocol version of a message, detected from the `jsonrpc` or `version` member of the
+/// object or of the first object of a batch.
+///
+/// Anything else is detected as JSON-RPC 2.0, whose decoder then rejects it.
+struct Detected(ProtocolVersion);
+
+impl<'de> de::Deserialize<'de> for Detected {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::DeserializeSeed::deserialize(VersionSeed { in_batch: false }, deserializer)
+            .map(Detected)
+    }
+}
+
+/// Detects the version of a message, or of a call or an output of a batch.
+#[derive(Clone, Copy)]
+struct VersionSeed {
+    in_batch: bool,
+}
+
+impl<'de> de::DeserializeSeed<'de> for VersionSeed {
+    type Value = ProtocolVersion;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserializer::deserialize_any(deserializer, self)
+    }
+}
+
+impl<'de> de::Visitor<'de> for VersionSeed {
+    type Value = ProtocolVersion;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(ProtocolVersion::V2_0)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let version = if self.in_batch {
+            None
+        } else {
+            seq.next_element_seed(VersionSeed { in_batch: true })?
+        };
+        while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+        Ok(version.unwrap_or(ProtocolVersion::V2_0))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        use self::version_field::Field;
+
+        let (mut jsonrpc, mut version) = (false, false);
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Jsonrpc => jsonrpc = true,
+                Field::Version => version = true,
+                Field::Other => {}
+            }
+            map.next_value::<de::IgnoredAny>()?;
+        }
+        Ok(if jsonrpc {
+            ProtocolVersion::V2_0
+        } else if version {
+            ProtocolVersion::V1_1
+        } else {
+            ProtocolVersion::V1_0
+        })
+    }
+}
+
+mod version_field {
+    use super::*;
+
+    /// The members which determine the version of a message; all the others are ignored.
+    pub enum Field {
+        Jsonrpc,
+        Version,
+        Other,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "jsonrpc" => Ok(Field::Jsonrpc),
+                "version" => Ok(Field::Version),
+                _ => Ok(Field::Other),
+            }
+        }
+    }
+}
+
+/// Represents a JSON-RPC 1.0, 1.1 or 2.0 request.
+///
+/// A request is decoded as JSON-RPC 2.0 if it has a `jsonrpc` member, as JSON-RPC 1.1 if it has
+/// a `version` member, as JSON-RPC 1.0 otherwise.
+///
+/// ```rust
+/// use jsonrpc_types::{AnyRequest, ProtocolVersion};
+///
+/// let request = r#"{"method":"getblockcount","params":[],"id":1}"#.parse::<AnyRequest>().unwrap();
+/// assert_eq!(request.version(), ProtocolVersion::V1_0);
+///
+/// let request = r#"{"version":"1.1","method":"getblockcount","id":1}"#.parse::<AnyRequest>().unwrap();
+/// assert_eq!(request.version(), ProtocolVersion::V1_1);
+///
+/// let request = r#"{"jsonrpc":"2.0","method":"getblockcount","id":1}"#.parse::<AnyRequest>().unwrap();
+/// assert_eq!(request.version(), ProtocolVersion::V2_0);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnyRequest {
+    /// JSON-RPC 1.0 request
+    V1(v1::Request),
+    /// JSON-RPC 1.1 request
+    V1_1(v1_1::Request),
+    /// JSON-RPC 2.0 request
+    V2(v2::Request),
+}
+
+impl fmt::Display for AnyRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`AnyRequest` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for AnyRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // The version is detected from the raw request, which is then deserialized only once.
+        let raw = <Box<RawValue> as de::Deserialize>::deserialize(deserializer)?;
+        match raw::decode(&raw)? {
+            Detected(ProtocolVersion::V1_0) => raw::decode(&raw).map(Self::V1),
+            Detected(ProtocolVersion::V1_1) => raw::decode(&raw).map(Self::V1_1),
+            Detected(ProtocolVersion::V2_0) => raw::decode(&raw).map(Self::V2),
+        }
+    }
+}
+
+impl FromStr for AnyRequest {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl From<v1::Request> for AnyRequest {
+    fn from(request: v1::Request) -> Self {
+        Self::V1(request)
+    }
+}
+
+impl From<v1_1::Request> for AnyRequest {
+    fn from(request: v1_1::Request) -> Self {
+        Self::V1_1(request)
+    }
+}
+
+impl From<v2::Request> for AnyRequest {
+    fn from(request: v2::Request) -> Self {
+        Self::V2(request)
+    }
+}
+
+impl AnyRequest {
+    /// Returns the protocol version of the request.
+    pub fn version(&self) -> ProtocolVersion {
+        match self {
+            Self::V1(_) => ProtocolVersion::V1_0,
+            Self::V1_1(_) => ProtocolVersion::V1_1,
+            Self::V2(_) => ProtocolVersion::V2_0,
+        }
+    }
+
+    /// Converts the JSON-RPC 2.0 response into the protocol version of the request.
+    ///
+    /// Fails if the request is JSON-RPC 1.0 and the response has a success with a Null result
+    /// or a Null id, which JSON-RPC 1.0 doesn't allow.
+    ///
+    /// ```rust
+    /// use jsonrpc_types::{AnyRequest, AnyResponse, Output, Response, Value};
+    ///
+    /// let request = r#"{"method":"getblockcount","params":[],"id":1}"#.parse::<AnyRequest>().unwrap();
+    /// let response = request
+    ///     .reply(Response::Single(Output::success(Value::from(100), 1.into())))
+    ///     .unwrap();
+    /// assert_eq!(response.to_string(), r#"{"result":100,"error":null,"id":1}"#);
+    /// ```
+    pub fn reply<T: Serialize>(
+        &self,
+        response: v2::Response<T>,
+    ) -> Result<AnyResponse<T>, ConversionError> {
+        match self {
+            Self::V1(_) => v1::Response::try_from(response).map(AnyResponse::V1),
+            Self::V1_1(_) => Ok(AnyResponse::V1_1(response.into())),
+            Self::V2(_) => Ok(AnyResponse::V2(response)),
+        }
+    }
+}
+
+/// Represents a JSON-RPC 1.0, 1.1 or 2.0 response.
+///
+/// A response is decoded as JSON-RPC 2.0 if it has a `jsonrpc` member, as JSON-RPC 1.1 if it has
+/// a `version` member, as JSON-RPC 1.0 otherwise.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnyResponse<T = Value> {
+    /// JSON-RPC 1.0 response
+    V1(v1::Response<T>),
+    /// JSON-RPC 1.1 response
+    V1_1(v1_1::Response<T>),
+    /// JSON-RPC 2.0 response
+    V2(v2::Response<T>),
+}
+
+impl<T: Serialize> fmt::Display for AnyResponse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`AnyResponse` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de, T: DeserializeOwned> de::Deserialize<'de> for AnyResponse<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // The version is detected from the raw response, which is then deserialized only once.
+        let raw = <Box<RawValue> as de::Deserialize>::deserialize(deserializer)?;
+        match raw::decode(&raw)? {
+            Detected(ProtocolVersion::V1_0) => raw::decode(&raw).map(Self::V1),
+            Detected(ProtocolVersion::V1_1) => raw::decode(&raw).map(Self::V1_1),
+            Detected(ProtocolVersion::V2_0) => raw::decode(&raw).map(Self::V2),
+        }
+    }
+}
+
+impl<T> From<v1::Response<T>> for AnyResponse<T> {
+    fn from(response: v1::Response<T>) -> Self {
+        Self::V1(response)
+    }
+}
+
+impl<T> From<v1_1::Response<T>> for AnyResponse<T> {
+    fn from(response: v1_1::Response<T>) -> Self {
+        Self::V1_1(response)
+    }
+}
+
+impl<T> From<v2::Response<T>> for AnyResponse<T> {
+    fn from(response: v2::Response<T>) -> Self {
+        Self::V2(response)
+    }
+}
+
+impl<T> AnyResponse<T> {
+    /// Returns the protocol version of the response.
+    pub fn version(&self) -> ProtocolVersion {
+        match self {
+            Self::V1(_) => ProtocolVersion::V1_0,
+            Self::V1_1(_) => ProtocolVersion::V1_1,
+            Self::V2(_) => ProtocolVersion::V2_0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, id::Id};
+
+    #[test]
+    fn any_request_detection() {
+        let mut named_params = serde_json::Map::new();
+        named_params.insert("a".into(), Value::from(1));
+        let cases = vec![
+            (
+                r#"{"method":"foo","params":[1],"id":1}"#,
+                AnyRequest::V1(v1::Request::Single(
+                    v1::MethodCall::new("foo", vec![Value::from(1)], 1.into()).into(),
+                )),
+            ),
+            (
+                r#"[{"method":"foo","params":[],"id":null}]"#,
+                AnyRequest::V1(v1::Request::Batch(vec![v1::Notification::new(
+                    "foo",
+                    vec![],
+                )
+                .into()])),
+            ),
+            (
+                r#"{"version":"1.1","method":"foo","params":{"a":1}}"#,
+                AnyRequest::V1_1(v1_1::Request::Single(
+                    v1_1::Notification::new("foo", named_params).into(),
+                )),
+            ),
+            (
+                r#"{"jsonrpc":"2.0","method":"foo","params":[1],"id":1}"#,
+                AnyRequest::V2(v2::Request::Single(
+                    v2::MethodCall::new("foo", (1,), 1.into()).into(),
+                )),
+            ),
+            (
+                r#"[{"jsonrpc":"2.0","method":"foo"}]"#,
+                AnyRequest::V2(v2::Request::Batch(vec![
+                    v2::Notification::new("foo", ()).into()
+                ])),
+            ),
+        ];
+        for (case, expect) in cases {
+            let request = case.parse::<AnyRequest>().unwrap();
+            assert_eq!(request, expect);
+            assert_eq!(request.to_string(), case);
+        }
+
+        let invalid_cases = vec![
+            r#"{"method":"foo","params":[],"id":1,"jsonrpc":"1.0"}"#,
+            r#"[{"method":"foo","params":[],"id":1},{"jsonrpc":"2.0","method":"foo"}]"#,
+            r#"{"version":"1.0","method":"foo","id":1}"#,
+            r#"1"#,
+            r#"[1]"#,
+        ];
+        for case in invalid_cases {
+            assert!(
+                case.parse::<AnyRequest>().is_err(),
+                "{} should be invalid",
+                case
+            );
+        }
+
+        // Errors keep their position in the request, and the error of the decoder is passed through.
+        let err = r#"{"jsonrpc":"2.0","method":"foo",}"#.parse::<AnyRequest>().unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 33));
+        assert_eq!(err.to_string().matches(" at line ").count(), 1);
+        let err = r#"{"method":"foo","params":[],"id":true}"#.parse::<AnyRequest>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "data did not match any variant of untagged enum Request"
+        );
+    }
+
+    #[test]
+    fn any_response_detection() {
+        let response: AnyResponse =
+            serde_json::from_str(r#"{"result":true,"error":null,"id":1}"#).unwrap();
+        assert_eq!(
+            response,
+            AnyResponse::V1(v1::Response::Single(v1::Output::success(
+                Value::Bool(true),
+                1.into()
+            )))
+        );
+        assert_eq!(response.version(), ProtocolVersion::V1_0);
+
+        let response: AnyResponse =
+            serde_json::from_str(r#"[{"jsonrpc":"2.0","result":true,"id":1}]"#).unwrap();
+        assert_eq!(
+            response,
+            AnyResponse::V2(v2::Response::Batch(vec![v2::Output::success(
+                Value::Bool(true),
+                1.into()
+            )]))
+        );
+        assert_eq!(response.version(), ProtocolVersion::V2_0);
+    }
+
+    #[test]
+    fn reply_in_request_version() {
+        let response = v2::Response::Batch(vec![
+            v2::Output::success(Value::Bool(true), 1.into()),
+            v2::Output::failure(Error::method_not_found(), Id::Null),
+        ]);
+
+        let request = r#"[{"method":"foo","params":[],"id":1}]"#.parse::<AnyRequest>().unwrap();
+        assert_eq!(
+            request.reply(response.clone()).unwrap().to_string(),
+            r#"[{"result":true,"error":null,"id":1},{"result":null,"error":{"code":-32601,"message":"Method not found"},"id":null}]"#
+        );
+
+        let request = r#"[{"version":"1.1","method":"foo","id":1}]"#.parse::<AnyRequest>().unwrap();
+        assert_eq!(
+            request.reply(response.clone()).unwrap().to_string(),
+            r#"[{"version":"1.1","result":true,"id":1},{"version":"1.1","error":{"name":"JSONRPCError","code":-32601,"message":"Method not found"},"id":null}]"#
+        );
+
+        let request = r#"[{"jsonrpc":"2.0","method":"foo","id":1}]"#.parse::<AnyRequest>().unwrap();
+        assert_eq!(
+            request.reply(response.clone()),
+            Ok(AnyResponse::V2(response))
+        );
+
+        let request = r#"{"method":"foo","params":[],"id":1}"#.parse::<AnyRequest>().unwrap();
+        let response = v2::Response::Single(v2::Output::success(Value::Null, 1.into()));
+        assert_eq!(request.reply(response), Err(ConversionError::NullResult));
+    }
+}
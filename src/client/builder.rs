@@ -0,0 +1,173 @@
+use super::id_generator::{CounterIdGenerator, IdGenerator};
+use crate::v2::{Call, MethodCall, Notification, Request, ToParams};
+
+/// Builds JSON-RPC 2.0 requests, taking the ids of method calls from an [`IdGenerator`].
+///
+/// Like [`MethodCall::new`], the builder panics if parameters can't be serialized into JSON.
+///
+/// ```rust
+/// use jsonrpc_types::client::{CounterIdGenerator, RequestBuilder};
+///
+/// let builder = RequestBuilder::new(CounterIdGenerator::new());
+/// let call = builder.method_call("add", (1, 2));
+/// assert_eq!(call.to_string(), r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1}"#);
+///
+/// let batch = builder
+///     .batch()
+///     .method_call("add", (3, 4))
+///     .notification("log", ("hello",))
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     batch.to_string(),
+///     r#"[{"jsonrpc":"2.0","method":"add","params":[3,4],"id":2},{"jsonrpc":"2.0","method":"log","params":["hello"]}]"#
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct RequestBuilder<G = CounterIdGenerator> {
+    generator: G,
+}
+
+impl<G: IdGenerator> RequestBuilder<G> {
+    /// Creates a request builder with the given id generator.
+    pub fn new(generator: G) -> Self {
+        Self { generator }
+    }
+
+    /// Returns the id generator of the builder.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Creates a method call with the next id.
+    pub fn method_call<M: Into<String>, P: ToParams>(&self, method: M, params: P) -> MethodCall {
+        MethodCall::new(method, params, self.generator.next_id())
+    }
+
+    /// Creates a notification.
+    pub fn notification<M: Into<String>, P: ToParams>(&self, method: M, params: P) -> Notification {
+        Notification::new(method, params)
+    }
+
+    /// Creates a single request of a method call with the next id.
+    pub fn request<M: Into<String>, P: ToParams>(&self, method: M, params: P) -> Request {
+        Request::Single(Call::MethodCall(self.method_call(method, params)))
+    }
+
+    /// Starts building a batch request.
+    pub fn batch(&self) -> BatchBuilder<'_, G> {
+        BatchBuilder {
+            builder: self,
+            calls: Vec::new(),
+        }
+    }
+}
+
+/// Builds a JSON-RPC 2.0 batch request, see [`RequestBuilder::batch`].
+#[derive(Debug)]
+pub struct BatchBuilder<'a, G> {
+    builder: &'a RequestBuilder<G>,
+    calls: Vec<Call>,
+}
+
+impl<'a, G: IdGenerator> BatchBuilder<'a, G> {
+    /// Appends a method call with the next id.
+    pub fn method_call<M: Into<String>, P: ToParams>(mut self, method: M, params: P) -> Self {
+        let call = self.builder.method_call(method, params);
+        self.calls.push(Call::MethodCall(call));
+        self
+    }
+
+    /// Appends a notification.
+    pub fn notification<M: Into<String>, P: ToParams>(mut self, method: M, params: P) -> Self {
+        let notification = self.builder.notification(method, params);
+        self.calls.push(Call::Notification(notification));
+        self
+    }
+
+    /// Returns the number of calls of the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Checks if the batch contains no calls.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Builds the batch request, or returns `None` if the batch contains no calls,
+    /// as an empty batch is an invalid request.
+    pub fn build(self) -> Option<Request> {
+        if self.calls.is_empty() {
+            None
+        } else {
+            Some(Request::Batch(self.calls))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        client::{RandomStringIdGenerator, UuidIdGenerator},
+        id::Id,
+        v2::{Params, Value},
+    };
+
+    #[test]
+    fn build_requests() {
+        let builder = RequestBuilder::new(CounterIdGenerator::starting_at(5));
+        assert_eq!(
+            builder.method_call("foo", ()),
+            MethodCall::new("foo", (), Id::Num(5))
+        );
+        assert_eq!(
+            builder.request("foo", vec![1]),
+            Request::Single(Call::MethodCall(MethodCall::new(
+                "foo",
+                vec![1],
+                Id::Num(6)
+            )))
+        );
+        assert_eq!(
+            builder.notification("bar", (true,)),
+            Notification::new("bar", Some(Params::Array(vec![Value::Bool(true)])))
+        );
+
+        let batch = builder
+            .batch()
+            .method_call("foo", ())
+            .notification("bar", ());
+        assert_eq!(batch.len(), 2);
+        assert_eq!(
+            batch.method_call("baz", ()).build(),
+            Some(Request::Batch(vec![
+                Call::MethodCall(MethodCall::new("foo", (), Id::Num(7))),
+                Call::Notification(Notification::new("bar", ())),
+                Call::MethodCall(MethodCall::new("baz", (), Id::Num(8))),
+            ]))
+        );
+        assert!(builder.batch().is_empty());
+        assert_eq!(builder.batch().build(), None);
+    }
+
+    #[test]
+    fn shared_id_generator() {
+        let generator: Arc<dyn IdGenerator> = Arc::new(UuidIdGenerator::new());
+        let builder1 = RequestBuilder::new(generator.clone());
+        let builder2 = RequestBuilder::new(generator);
+        assert_ne!(
+            builder1.method_call("foo", ()).id,
+            builder2.method_call("foo", ()).id
+        );
+
+        let builder = RequestBuilder::new(RandomStringIdGenerator::with_len(10));
+        assert_eq!(
+            builder.method_call("foo", ()).id.as_str().unwrap().len(),
+            10
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    id::Id,
+    v2::{Failure, MethodCall, MethodCallRequest, Output, Response},
+};
+
+/// Something unexpected found while matching a response to the method calls that were sent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Anomaly<T = Value> {
+    /// More than one method call was sent with this id, so their outputs can't be told apart.
+    /// The outputs with this id are assigned to these method calls in the order they are received.
+    DuplicateRequestId(Id),
+    /// No output was received for the method call with this id.
+    MissingId(Id),
+    /// An output was received again for the same method call, the first one is kept.
+    DuplicateId(Output<T>),
+    /// An output was received whose id doesn't match any method call.
+    UnexpectedId(Output<T>),
+    /// A failure with a null id was received, i.e. the Server could not detect the id of
+    /// the method call, for example because of a parse error.
+    NullIdFailure(Failure),
+}
+
+/// The outputs of a response matched to the method calls that were sent, see [`correlate`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Correlation<T = Value> {
+    /// The id and the output of each method call, in the order the calls were sent.
+    pub outputs: Vec<(Id, Option<Output<T>>)>,
+    /// The anomalies found in the response.
+    pub anomalies: Vec<Anomaly<T>>,
+}
+
+impl<T> Correlation<T> {
+    /// Checks if every method call has exactly one output and nothing unexpected was received.
+    pub fn is_complete(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Returns the output of the method call with the given id.
+    pub fn get(&self, id: &Id) -> Option<&Output<T>> {
+        self.outputs
+            .iter()
+            .find(|(call_id, _)| call_id == id)
+            .and_then(|(_, output)| output.as_ref())
+    }
+}
+
+/// Matches the outputs of a response to the method calls of the request by id.
+///
+/// The Server may reply to a batch in any order, so the outputs are matched by id rather than
+/// by position. The ids of the method calls are expected to be unique within the request,
+/// the ids which are not are reported as [`Anomaly::DuplicateRequestId`].
+///
+/// ```rust
+/// use jsonrpc_types::{client::{correlate, Anomaly}, MethodCall, MethodCallRequest, Output, Response, Value};
+///
+/// let request = MethodCallRequest::Batch(vec![
+///     MethodCall::new("foo", (), 1.into()),
+///     MethodCall::new("bar", (), 2.into()),
+/// ]);
+/// let response = Response::Batch(vec![
+///     Output::success(Value::Bool(true), 2.into()),
+///     Output::success(Value::Bool(false), 3.into()),
+/// ]);
+///
+/// let correlation = correlate(&request, response);
+/// assert_eq!(correlation.get(&2.into()), Some(&Output::success(Value::Bool(true), 2.into())));
+/// assert_eq!(
+///     correlation.anomalies,
+///     vec![
+///         Anomaly::UnexpectedId(Output::success(Value::Bool(false), 3.into())),
+///         Anomaly::MissingId(1.into()),
+///     ]
+/// );
+/// ```
+pub fn correlate<T: Serialize + DeserializeOwned>(
+    request: &MethodCallRequest,
+    response: Response<T>,
+) -> Correlation<T> {
+    let calls = match request {
+        MethodCallRequest::Single(call) => std::slice::from_ref(call),
+        MethodCallRequest::Batch(calls) => calls.as_slice(),
+    };
+    let outputs = match response {
+        Response::Single(output) => vec![output],
+        Response::Batch(outputs) => outputs,
+    };
+
+    let mut matched = calls
+        .iter()
+        .map(|call: &MethodCall| (call.id.clone(), None))
+        .collect::<Vec<(Id, Option<Output<T>>)>>();
+    let mut anomalies = Vec::new();
+    let mut index = HashMap::<Id, Vec<usize>>::new();
+    for (i, (id, _)) in matched.iter().enumerate() {
+        let indices = index.entry(id.clone()).or_default();
+        indices.push(i);
+        if indices.len() == 2 {
+            anomalies.push(Anomaly::DuplicateRequestId(id.clone()));
+        }
+    }
+
+    for output in outputs {
+        let id = output.id();
+        match (output, index.get(&id)) {
+            (Output::Failure(failure), _) if id.is_null() => {
+                anomalies.push(Anomaly::NullIdFailure(failure))
+            }
+            (output, None) => anomalies.push(Anomaly::UnexpectedId(output)),
+            (output, Some(indices)) => match indices.iter().find(|&&i| matched[i].1.is_none()) {
+                Some(&i) => matched[i].1 = Some(output),
+                None => anomalies.push(Anomaly::DuplicateId(output)),
+            },
+        }
+    }
+    anomalies.extend(
+        matched
+            .iter()
+            .filter(|(_, output)| output.is_none())
+            .map(|(id, _)| Anomaly::MissingId(id.clone())),
+    );
+
+    Correlation {
+        outputs: matched,
+        anomalies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn correlate_batch_response() {
+        let request = MethodCallRequest::Batch(vec![
+            MethodCall::new("foo", (), 1.into()),
+            MethodCall::new("bar", (), "a".into()),
+            MethodCall::new("baz", (), 3.into()),
+        ]);
+
+        let response = Response::Batch(vec![
+            Output::success(3u64, 3.into()),
+            Output::failure(Error::internal_error(), "a".into()),
+            Output::success(1u64, 1.into()),
+        ]);
+        let correlation = correlate(&request, response);
+        assert!(correlation.is_complete());
+        assert_eq!(
+            correlation.outputs,
+            vec![
+                (Id::Num(1), Some(Output::success(1, 1.into()))),
+                (
+                    Id::Str("a".into()),
+                    Some(Output::failure(Error::internal_error(), "a".into()))
+                ),
+                (Id::Num(3), Some(Output::success(3, 3.into()))),
+            ]
+        );
+
+        let response = Response::Batch(vec![
+            Output::success(1u64, 1.into()),
+            Output::success(2u64, 1.into()),
+            Output::success(4u64, 4.into()),
+            Output::failure(Error::invalid_request(), Id::Null),
+        ]);
+        let correlation = correlate(&request, response);
+        assert!(!correlation.is_complete());
+        assert_eq!(
+            correlation.get(&1.into()),
+            Some(&Output::success(1, 1.into()))
+        );
+        assert_eq!(correlation.get(&3.into()), None);
+        assert_eq!(
+            correlation.anomalies,
+            vec![
+                Anomaly::DuplicateId(Output::success(2, 1.into())),
+                Anomaly::UnexpectedId(Output::success(4, 4.into())),
+                Anomaly::NullIdFailure(Failure::new(Error::invalid_request(), Id::Null)),
+                Anomaly::MissingId(Id::Str("a".into())),
+                Anomaly::MissingId(Id::Num(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn correlate_duplicate_request_ids() {
+        let request = MethodCallRequest::Batch(vec![
+            MethodCall::new("foo", (), 1.into()),
+            MethodCall::new("bar", (), 1.into()),
+            MethodCall::new("baz", (), 2.into()),
+        ]);
+
+        let response = Response::Batch(vec![
+            Output::success(1u64, 1.into()),
+            Output::success(2u64, 2.into()),
+            Output::success(3u64, 1.into()),
+        ]);
+        let correlation = correlate(&request, response);
+        assert_eq!(
+            correlation.outputs,
+            vec![
+                (Id::Num(1), Some(Output::success(1, 1.into()))),
+                (Id::Num(1), Some(Output::success(3, 1.into()))),
+                (Id::Num(2), Some(Output::success(2, 2.into()))),
+            ]
+        );
+        assert_eq!(
+            correlation.anomalies,
+            vec![Anomaly::DuplicateRequestId(Id::Num(1))]
+        );
+
+        let response = Response::Batch(vec![
+            Output::success(1u64, 1.into()),
+            Output::success(2u64, 2.into()),
+        ]);
+        let correlation = correlate(&request, response);
+        assert_eq!(
+            correlation.anomalies,
+            vec![
+                Anomaly::DuplicateRequestId(Id::Num(1)),
+                Anomaly::MissingId(Id::Num(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn correlate_single_response() {
+        let request = MethodCallRequest::Single(MethodCall::new("foo", (), 1.into()));
+
+        let correlation = correlate(&request, Response::Single(Output::success(true, 1.into())));
+        assert!(correlation.is_complete());
+        assert_eq!(
+            correlation.get(&1.into()),
+            Some(&Output::success(true, 1.into()))
+        );
+
+        let response = Response::<bool>::Single(Output::failure(Error::parse_error(), Id::Null));
+        let correlation = correlate(&request, response);
+        assert_eq!(
+            correlation.anomalies,
+            vec![
+                Anomaly::NullIdFailure(Failure::new(Error::parse_error(), Id::Null)),
+                Anomaly::MissingId(Id::Num(1)),
+            ]
+        );
+    }
+}
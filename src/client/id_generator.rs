@@ -0,0 +1,279 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::id::Id;
+
+/// Generates the ids of JSON-RPC method calls.
+///
+/// Generators are shared between concurrent tasks, so each call of [`IdGenerator::next_id`]
+/// should return an id that is unlikely to collide with the ids returned before.
+///
+/// Only [`CounterIdGenerator`] guarantees that the ids are unique (until the counter wraps
+/// around), the random generators make collisions unlikely but not impossible.
+pub trait IdGenerator: Send + Sync {
+    /// Returns the next id.
+    fn next_id(&self) -> Id;
+}
+
+impl<G: IdGenerator + ?Sized> IdGenerator for &G {
+    fn next_id(&self) -> Id {
+        (**self).next_id()
+    }
+}
+
+impl<G: IdGenerator + ?Sized> IdGenerator for Box<G> {
+    fn next_id(&self) -> Id {
+        (**self).next_id()
+    }
+}
+
+impl<G: IdGenerator + ?Sized> IdGenerator for Arc<G> {
+    fn next_id(&self) -> Id {
+        (**self).next_id()
+    }
+}
+
+/// Generates unique numeric ids from an atomic counter, starting at 1 by default.
+#[derive(Debug)]
+pub struct CounterIdGenerator {
+    next: AtomicU64,
+}
+
+impl Default for CounterIdGenerator {
+    fn default() -> Self {
+        Self::starting_at(1)
+    }
+}
+
+impl CounterIdGenerator {
+    /// Creates a counter that starts at 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a counter that starts at the given number.
+    pub fn starting_at(start: u64) -> Self {
+        Self {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl IdGenerator for CounterIdGenerator {
+    fn next_id(&self) -> Id {
+        Id::Num(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A source of random numbers, keyed by the random state of the standard library.
+///
+/// This is not a cryptographically secure generator, the ids are not meant to be secrets.
+struct RandomSource {
+    state: RandomState,
+    counter: AtomicU64,
+}
+
+impl RandomSource {
+    fn new() -> Self {
+        Self {
+            state: RandomState::new(),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    fn next_u64(&self) -> u64 {
+        let mut hasher = self.state.build_hasher();
+        hasher.write_u64(self.counter.fetch_add(1, Ordering::Relaxed));
+        hasher.finish()
+    }
+}
+
+impl fmt::Debug for RandomSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RandomSource").finish_non_exhaustive()
+    }
+}
+
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Random bytes below this bound are mapped onto the alphanumeric characters, the others are
+/// rejected, so that every character is equally likely.
+const ALPHANUMERIC_BOUND: usize = 256 / ALPHANUMERIC.len() * ALPHANUMERIC.len();
+
+/// Generates random alphanumeric string ids, of 16 characters by default.
+///
+/// Shorter ids are more likely to collide: with 8 characters, there are 62^8 ids.
+#[derive(Debug)]
+pub struct RandomStringIdGenerator {
+    len: usize,
+    source: RandomSource,
+}
+
+impl Default for RandomStringIdGenerator {
+    fn default() -> Self {
+        Self::with_len(16)
+    }
+}
+
+impl RandomStringIdGenerator {
+    /// Creates a generator of 16 characters long ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a generator of ids with the given number of characters.
+    ///
+    /// Ids of less than 8 characters are likely to collide, unless few calls are in flight.
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            len,
+            source: RandomSource::new(),
+        }
+    }
+}
+
+impl IdGenerator for RandomStringIdGenerator {
+    fn next_id(&self) -> Id {
+        let mut id = String::with_capacity(self.len);
+        while id.len() < self.len {
+            let bytes = self.source.next_u64().to_le_bytes();
+            for &byte in bytes
+                .iter()
+                .filter(|&&byte| usize::from(byte) < ALPHANUMERIC_BOUND)
+            {
+                if id.len() == self.len {
+                    break;
+                }
+                let index = usize::from(byte) % ALPHANUMERIC.len();
+                id.push(char::from(ALPHANUMERIC[index]));
+            }
+        }
+        Id::Str(id)
+    }
+}
+
+/// Generates random string ids formatted like version 4 UUIDs,
+/// e.g. `"0e5c2d3a-58e1-4b5e-9f0c-6a1d2b3c4d5e"`.
+#[derive(Debug)]
+pub struct UuidIdGenerator {
+    source: RandomSource,
+}
+
+impl Default for UuidIdGenerator {
+    fn default() -> Self {
+        Self {
+            source: RandomSource::new(),
+        }
+    }
+}
+
+impl UuidIdGenerator {
+    /// Creates a generator of UUID-like ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for UuidIdGenerator {
+    fn next_id(&self) -> Id {
+        let high = self.source.next_u64();
+        let low = self.source.next_u64();
+        // Sets the version (4) and the variant (RFC 4122) bits.
+        let high = (high & !0xf000) | 0x4000;
+        let low = (low & !(0b11 << 62)) | (0b10 << 62);
+        Id::Str(format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xffff,
+            high & 0xffff,
+            low >> 48,
+            low & 0xffff_ffff_ffff
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn counter_id_generator() {
+        let generator = CounterIdGenerator::new();
+        assert_eq!(generator.next_id(), Id::Num(1));
+        assert_eq!(generator.next_id(), Id::Num(2));
+
+        let generator = Arc::new(CounterIdGenerator::starting_at(10));
+        let handles = (0..4)
+            .map(|_| {
+                let generator = generator.clone();
+                std::thread::spawn(move || {
+                    (0..100).map(|_| generator.next_id()).collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        let ids = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 400);
+        assert_eq!(generator.next_id(), Id::Num(410));
+    }
+
+    #[test]
+    fn random_string_id_generator() {
+        let generator = RandomStringIdGenerator::with_len(8);
+        let ids = (0..100)
+            .map(|_| generator.next_id())
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 100);
+        for id in ids {
+            let id = id.as_str().unwrap();
+            assert_eq!(id.len(), 8);
+            assert!(id.bytes().all(|c| c.is_ascii_alphanumeric()));
+        }
+    }
+
+    #[test]
+    fn short_random_string_id() {
+        let generator = RandomStringIdGenerator::with_len(1);
+        assert_eq!(generator.next_id().as_str().unwrap().len(), 1);
+        assert_eq!(
+            RandomStringIdGenerator::with_len(0).next_id(),
+            Id::Str(String::new())
+        );
+
+        // All the characters are drawn.
+        let chars = (0..2000)
+            .map(|_| generator.next_id().as_str().unwrap().to_owned())
+            .collect::<HashSet<_>>();
+        assert_eq!(chars.len(), ALPHANUMERIC.len());
+    }
+
+    #[test]
+    fn uuid_id_generator() {
+        let generator = UuidIdGenerator::new();
+        let ids = (0..100)
+            .map(|_| generator.next_id())
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 100);
+        for id in ids {
+            let id = id.as_str().unwrap();
+            let groups = id.split('-').map(str::len).collect::<Vec<_>>();
+            assert_eq!(groups, vec![8, 4, 4, 4, 12]);
+            assert_eq!(&id[14..15], "4");
+            assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+            assert!(id
+                .bytes()
+                .all(|c| c == b'-' || matches!(c, b'0'..=b'9' | b'a'..=b'f')));
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// JSON-RPC 2.0 request builder
+mod builder;
+/// Correlation of responses to the method calls that were sent
+mod correlate;
+/// Generators of method call ids
+mod id_generator;
+
+pub use self::{
+    builder::{BatchBuilder, RequestBuilder},
+    correlate::{correlate, Anomaly, Correlation},
+    id_generator::{CounterIdGenerator, IdGenerator, RandomStringIdGenerator, UuidIdGenerator},
+};
@@ -0,0 +1,195 @@
+use bytes::{Buf, BytesMut};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::{parse_frame, CodecError, ContentLengthCodec, LengthPrefixCodec, LineCodec};
+
+impl<T: DeserializeOwned> Decoder for LineCodec<T> {
+    type Item = T;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(src);
+        let item = parse_frame(src, frame);
+        src.advance(consumed);
+        item
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_last_frame(src);
+        let item = parse_frame(src, frame);
+        src.advance(consumed);
+        item
+    }
+}
+
+impl<T, U: Serialize> Encoder<U> for LineCodec<T> {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(&item)?);
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for ContentLengthCodec<T> {
+    type Item = T;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(src);
+        let item = parse_frame(src, frame);
+        src.advance(consumed);
+        item
+    }
+}
+
+impl<T, U: Serialize> Encoder<U> for ContentLengthCodec<T> {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(&item)?);
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for LengthPrefixCodec<T> {
+    type Item = T;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(src);
+        let item = parse_frame(src, frame);
+        src.advance(consumed);
+        item
+    }
+}
+
+impl<T, U: Serialize> Encoder<U> for LengthPrefixCodec<T> {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(&item)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::{duplex, AsyncWriteExt};
+    use tokio_util::codec::{Framed, FramedRead};
+
+    use super::*;
+    use crate::v2::{
+        MethodCall, Output, Request, Response, SubscriptionNotification,
+        SubscriptionNotificationParams, Value,
+    };
+
+    #[tokio::test]
+    async fn line_framed_requests_and_responses() {
+        let (client, server) = duplex(64);
+        let mut client = Framed::new(client, LineCodec::<Response>::new());
+        let mut server = Framed::new(server, LineCodec::<Request>::new());
+
+        let request = Request::Single(MethodCall::new("foo", (1,), 1.into()).into());
+        client.send(&request).await.unwrap();
+        assert_eq!(server.next().await.unwrap().unwrap(), request);
+
+        let response = Response::Single(Output::success(Value::Bool(true), 1.into()));
+        server.send(&response).await.unwrap();
+        assert_eq!(client.next().await.unwrap().unwrap(), response);
+
+        drop(server);
+        assert!(client.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn content_length_framed_requests_and_responses() {
+        let (client, server) = duplex(16);
+        let mut client = Framed::new(client, ContentLengthCodec::<Response>::new());
+        let mut server = Framed::new(server, ContentLengthCodec::<Request>::new());
+
+        let requests = vec![
+            Request::Single(MethodCall::new("foo", (), 1.into()).into()),
+            Request::Batch(vec![MethodCall::new("bar", (), 2.into()).into()]),
+        ];
+        let send = async {
+            for request in &requests {
+                client.send(request).await.unwrap();
+            }
+            client
+        };
+        let receive = async {
+            let mut received = Vec::new();
+            for _ in 0..requests.len() {
+                received.push(server.next().await.unwrap().unwrap());
+            }
+            (server, received)
+        };
+        let (mut client, (mut server, received)) = futures::join!(send, receive);
+        assert_eq!(received, requests);
+
+        let notification = SubscriptionNotification::new(
+            "subscription",
+            SubscriptionNotificationParams::new(1.into(), Value::from("foo")),
+        );
+        let send = server.send(&notification);
+        let mut client_for_notifications = FramedRead::new(
+            client.get_mut(),
+            ContentLengthCodec::<SubscriptionNotification>::new(),
+        );
+        let (sent, received) = futures::join!(send, client_for_notifications.next());
+        sent.unwrap();
+        assert_eq!(received.unwrap().unwrap(), notification);
+    }
+
+    #[tokio::test]
+    async fn length_prefix_framed_requests_and_responses() {
+        let (client, server) = duplex(16);
+        let mut client = Framed::new(client, LengthPrefixCodec::<Response>::new());
+        let mut server = Framed::new(server, LengthPrefixCodec::<Request>::new());
+
+        let request = Request::Single(MethodCall::new("foo", (1,), 1.into()).into());
+        let (sent, received) = futures::join!(client.send(&request), server.next());
+        sent.unwrap();
+        assert_eq!(received.unwrap().unwrap(), request);
+
+        let response = Response::Single(Output::success(Value::Bool(true), 1.into()));
+        let (sent, received) = futures::join!(server.send(&response), client.next());
+        sent.unwrap();
+        assert_eq!(received.unwrap().unwrap(), response);
+    }
+
+    #[tokio::test]
+    async fn framed_decode_errors() {
+        let (mut client, server) = duplex(16);
+        let mut server = FramedRead::new(server, LineCodec::<Request>::with_max_frame_size(32));
+
+        let write = async move {
+            client
+                .write_all(b"{\"foo\":1}\n{\"jsonrpc\":\"2.0\",\"method\":\"foo\"}\n")
+                .await
+                .unwrap();
+            client.write_all(&[b'x'; 40]).await.unwrap();
+            client.write_all(b"\n").await.unwrap();
+        };
+        let read = async {
+            // The stream yields `None` once after an error, and then carries on.
+            let mut items = Vec::new();
+            for _ in 0..5 {
+                items.push(server.next().await);
+            }
+            items
+        };
+        let (_, items) = futures::join!(write, read);
+        assert!(matches!(items[0], Some(Err(CodecError::Json(_)))));
+        assert!(items[1].is_none());
+        assert!(matches!(items[2], Some(Ok(_))));
+        assert!(matches!(
+            items[3],
+            Some(Err(CodecError::FrameTooLarge { max: 32 }))
+        ));
+        assert!(items[4].is_none());
+    }
+}
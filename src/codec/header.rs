@@ -0,0 +1,417 @@
+use std::{fmt, marker::PhantomData, ops::Range, str};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{parse_frame, CodecError, HeaderError, DEFAULT_MAX_FRAME_SIZE};
+
+/// The default maximum size in bytes of a header block, i.e. 8 KiB.
+pub const DEFAULT_MAX_HEADER_SIZE: usize = 8 * 1024;
+
+/// The default content type of the Language Server Protocol.
+pub const DEFAULT_CONTENT_TYPE: &str = "application/vscode-jsonrpc; charset=utf-8";
+
+const HEADER_END: &[u8] = b"\r\n\r\n";
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    /// Waiting for a complete header block.
+    Header,
+    /// Waiting for the content of the given length.
+    Content(usize),
+    /// Skipping the remaining bytes of an oversized content.
+    Discard(usize),
+}
+
+/// Splits a byte stream into JSON-RPC messages of type `T` framed by `Content-Length` headers,
+/// as used by the Language Server Protocol and the Debug Adapter Protocol.
+///
+/// ```text
+/// Content-Length: 39\r\n
+/// Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n
+/// \r\n
+/// {"jsonrpc":"2.0","method":"foo","id":1}
+/// ```
+///
+/// The `Content-Length` header is required, the `Content-Type` header is optional but its charset,
+/// if any, must be UTF-8. Other headers are ignored.
+///
+/// An oversized content is reported once and then skipped. After any other error, the codec can't
+/// tell where the next message starts, so the stream should be closed.
+///
+/// With the `tokio` feature enabled, the codec implements `tokio_util::codec::Decoder` for `T`
+/// and `Encoder` for any serializable message, like [`LineCodec`](super::LineCodec).
+///
+/// ```rust
+/// use jsonrpc_types::{codec::ContentLengthCodec, Request};
+///
+/// let mut codec = ContentLengthCodec::<Request>::new();
+/// let mut buf = b"Content-Length: 39\r\n\r\n".to_vec();
+/// assert!(codec.decode(&mut buf).unwrap().is_none());
+///
+/// buf.extend_from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":1}"#);
+/// let request = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(request.to_string(), r#"{"jsonrpc":"2.0","method":"foo","id":1}"#);
+/// ```
+pub struct ContentLengthCodec<T> {
+    max_frame_size: usize,
+    max_header_size: usize,
+    content_type: Option<String>,
+    state: State,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for ContentLengthCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentLengthCodec")
+            .field("max_frame_size", &self.max_frame_size)
+            .field("max_header_size", &self.max_header_size)
+            .field("content_type", &self.content_type)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T> Clone for ContentLengthCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_size: self.max_frame_size,
+            max_header_size: self.max_header_size,
+            content_type: self.content_type.clone(),
+            state: self.state,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ContentLengthCodec<T> {
+    fn default() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
+impl<T> ContentLengthCodec<T> {
+    /// Creates a codec with the default maximum frame and header sizes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a codec with the given maximum content size in bytes.
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            max_frame_size,
+            max_header_size: DEFAULT_MAX_HEADER_SIZE,
+            content_type: None,
+            state: State::Header,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the maximum size in bytes of a header block, including the empty line.
+    pub fn max_header_size(mut self, max_header_size: usize) -> Self {
+        self.max_header_size = max_header_size;
+        self
+    }
+
+    /// Emits the `Content-Type` header with the given value when encoding,
+    /// e.g. [`DEFAULT_CONTENT_TYPE`].
+    pub fn content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Returns the maximum content size in bytes.
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Encodes the message as compact JSON preceded by its header block, appending it to `dst`.
+    pub fn encode<U: Serialize>(&mut self, item: &U, dst: &mut Vec<u8>) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(item)?);
+        Ok(())
+    }
+
+    /// Looks for the next message at the front of the buffer.
+    ///
+    /// Returns the number of bytes that must be consumed from the buffer, along with the range
+    /// of the content of the next message if it's complete.
+    pub(crate) fn split_frame(
+        &mut self,
+        buf: &[u8],
+    ) -> (usize, Result<Option<Range<usize>>, CodecError>) {
+        let mut consumed = 0;
+        loop {
+            let rest = &buf[consumed..];
+            match self.state {
+                State::Header => {
+                    let end = match rest.windows(HEADER_END.len()).position(|w| w == HEADER_END) {
+                        Some(end) if end + HEADER_END.len() <= self.max_header_size => end,
+                        Some(_) => return (buf.len(), Err(self.header_too_large())),
+                        None if rest.len() >= self.max_header_size => {
+                            return (buf.len(), Err(self.header_too_large()))
+                        }
+                        None => return (consumed, Ok(None)),
+                    };
+                    consumed += end + HEADER_END.len();
+                    let len = match parse_header(&rest[..end]) {
+                        Ok(len) => len,
+                        Err(err) => return (consumed, Err(CodecError::Header(err))),
+                    };
+                    if len > self.max_frame_size {
+                        self.state = State::Discard(len);
+                        return (consumed, Err(self.frame_too_large()));
+                    }
+                    self.state = State::Content(len);
+                }
+                State::Content(len) => {
+                    if rest.len() < len {
+                        return (consumed, Ok(None));
+                    }
+                    self.state = State::Header;
+                    return (consumed + len, Ok(Some(consumed..consumed + len)));
+                }
+                State::Discard(len) => {
+                    if rest.len() < len {
+                        self.state = State::Discard(len - rest.len());
+                        return (buf.len(), Ok(None));
+                    }
+                    consumed += len;
+                    self.state = State::Header;
+                }
+            }
+        }
+    }
+
+    /// Serializes the message into compact JSON preceded by its header block.
+    pub(crate) fn to_frame<U: Serialize>(&self, item: &U) -> Result<Vec<u8>, CodecError> {
+        let content = serde_json::to_vec(item)?;
+        if content.len() > self.max_frame_size {
+            return Err(self.frame_too_large());
+        }
+        let mut frame = format!("Content-Length: {}\r\n", content.len());
+        if let Some(content_type) = &self.content_type {
+            frame.push_str("Content-Type: ");
+            frame.push_str(content_type);
+            frame.push_str("\r\n");
+        }
+        frame.push_str("\r\n");
+        let mut frame = frame.into_bytes();
+        frame.extend_from_slice(&content);
+        Ok(frame)
+    }
+
+    fn frame_too_large(&self) -> CodecError {
+        CodecError::FrameTooLarge {
+            max: self.max_frame_size,
+        }
+    }
+
+    fn header_too_large(&self) -> CodecError {
+        CodecError::Header(HeaderError::TooLarge {
+            max: self.max_header_size,
+        })
+    }
+}
+
+impl<T: DeserializeOwned> ContentLengthCodec<T> {
+    /// Decodes the next message at the front of the buffer, removing it from the buffer.
+    ///
+    /// Returns `None` if the buffer doesn't contain a complete message yet.
+    pub fn decode(&mut self, buf: &mut Vec<u8>) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(buf);
+        let item = parse_frame(buf, frame);
+        buf.drain(..consumed);
+        item
+    }
+}
+
+/// Parses the header block, without the trailing empty line, returning the content length.
+fn parse_header(header: &[u8]) -> Result<usize, HeaderError> {
+    let header = str::from_utf8(header)
+        .map_err(|_| HeaderError::Malformed("header is not valid UTF-8".into()))?;
+    let mut content_length = None;
+    for line in header.split("\r\n") {
+        let (name, value) = match line.find(':') {
+            Some(colon) => (line[..colon].trim(), line[colon + 1..].trim()),
+            None => return Err(HeaderError::Malformed(line.into())),
+        };
+        if name.eq_ignore_ascii_case("Content-Length") {
+            if content_length.is_some() {
+                return Err(HeaderError::DuplicateContentLength);
+            }
+            let len = value
+                .parse::<usize>()
+                .map_err(|_| HeaderError::InvalidContentLength(value.into()))?;
+            content_length = Some(len);
+        } else if name.eq_ignore_ascii_case("Content-Type") {
+            check_charset(value)?;
+        }
+    }
+    content_length.ok_or(HeaderError::MissingContentLength)
+}
+
+/// Checks that the charset parameter of the content type, if any, is UTF-8.
+fn check_charset(content_type: &str) -> Result<(), HeaderError> {
+    let charset = content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_at(param.find('=')?);
+        if name.trim().eq_ignore_ascii_case("charset") {
+            Some(value[1..].trim().trim_matches('"'))
+        } else {
+            None
+        }
+    });
+    match charset {
+        // `utf8` is accepted for backwards compatibility.
+        Some(charset)
+            if !charset.eq_ignore_ascii_case("utf-8") && !charset.eq_ignore_ascii_case("utf8") =>
+        {
+            Err(HeaderError::UnsupportedCharset(charset.into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2;
+
+    fn frame(header: &str, content: &str) -> Vec<u8> {
+        [header.as_bytes(), b"\r\n", content.as_bytes()].concat()
+    }
+
+    #[test]
+    fn decode_partial_frames() {
+        let content = r#"{"jsonrpc":"2.0","method":"foo","id":1}"#;
+        let input = [
+            frame("Content-Length: 39\r\n", content),
+            frame(
+                "content-type: application/vscode-jsonrpc; charset=\"UTF-8\"\r\nContent-Length:39\r\n",
+                content,
+            ),
+            frame(
+                "Content-Length: 39\r\nContent-Type: application/json; charset=utf8\r\n",
+                content,
+            ),
+            frame(
+                "Content-Length: 39\r\nContent-Type: application/json; foo=bar; Charset=utf-8\r\n",
+                content,
+            ),
+        ]
+        .concat();
+
+        // Feeds the input byte by byte.
+        let mut codec = ContentLengthCodec::<v2::Request>::new();
+        let mut buf = Vec::new();
+        let mut requests = Vec::new();
+        for &b in &input {
+            buf.push(b);
+            while let Some(request) = codec.decode(&mut buf).unwrap() {
+                requests.push(request);
+            }
+        }
+        assert!(buf.is_empty());
+        assert_eq!(requests.len(), 4);
+        for request in requests {
+            assert_eq!(request.to_string(), content);
+        }
+    }
+
+    #[test]
+    fn decode_malformed_header() {
+        let cases = vec![
+            (
+                "Content-Type: application/json\r\n",
+                HeaderError::MissingContentLength,
+            ),
+            (
+                "Content-Length: -1\r\n",
+                HeaderError::InvalidContentLength("-1".into()),
+            ),
+            (
+                "Content-Length: 2\r\nContent-Length: 2\r\n",
+                HeaderError::DuplicateContentLength,
+            ),
+            (
+                "Content-Length 2\r\n",
+                HeaderError::Malformed("Content-Length 2".into()),
+            ),
+            (
+                "Content-Length: 2\r\nContent-Type: text/plain; charset=latin1\r\n",
+                HeaderError::UnsupportedCharset("latin1".into()),
+            ),
+            (
+                "Content-Length: 2\r\nContent-Type: application/json; foo=bar; charset=latin1\r\n",
+                HeaderError::UnsupportedCharset("latin1".into()),
+            ),
+        ];
+        for (header, expect) in cases {
+            let mut codec = ContentLengthCodec::<v2::Request>::new();
+            let mut buf = frame(header, "{}");
+            match codec.decode(&mut buf) {
+                Err(CodecError::Header(err)) => assert_eq!(err, expect),
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        let mut codec = ContentLengthCodec::<v2::Request>::new().max_header_size(32);
+        let mut buf = vec![b'x'; 32];
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::Header(HeaderError::TooLarge { max: 32 }))
+        ));
+    }
+
+    #[test]
+    fn decode_oversized_content() {
+        let mut codec = ContentLengthCodec::<v2::Request>::with_max_frame_size(32);
+        let mut buf = frame("Content-Length: 40\r\n", &"x".repeat(30));
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::FrameTooLarge { max: 32 })
+        ));
+
+        // The rest of the oversized content is skipped.
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&"x".repeat(10).into_bytes());
+        buf.extend_from_slice(&frame(
+            "Content-Length: 32\r\n",
+            r#"{"jsonrpc":"2.0","method":"foo"}"#,
+        ));
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn encode_frames() {
+        let output = v2::Output::success(v2::Value::Bool(true), 1.into());
+
+        let mut codec = ContentLengthCodec::<v2::Request>::new();
+        let mut dst = Vec::new();
+        codec.encode(&output, &mut dst).unwrap();
+        assert_eq!(
+            dst,
+            frame(
+                "Content-Length: 38\r\n",
+                r#"{"jsonrpc":"2.0","result":true,"id":1}"#
+            )
+        );
+
+        let mut codec = ContentLengthCodec::<v2::Request>::new().content_type(DEFAULT_CONTENT_TYPE);
+        let mut dst = Vec::new();
+        codec.encode(&output, &mut dst).unwrap();
+        assert_eq!(
+            dst,
+            frame(
+                "Content-Length: 38\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n",
+                r#"{"jsonrpc":"2.0","result":true,"id":1}"#
+            )
+        );
+
+        let mut codec = ContentLengthCodec::<v2::Request>::with_max_frame_size(16);
+        assert!(matches!(
+            codec.encode(&output, &mut Vec::new()),
+            Err(CodecError::FrameTooLarge { max: 16 })
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use std::{convert::TryFrom, fmt, marker::PhantomData, ops::Range};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{parse_frame, CodecError, DEFAULT_MAX_FRAME_SIZE};
+
+/// The size in bytes of the length prefix of a frame.
+const PREFIX_SIZE: usize = 4;
+
+/// Splits a byte stream into JSON-RPC messages of type `T`, each prefixed with its length
+/// in bytes as a 32-bit big-endian unsigned integer.
+///
+/// Bytes are appended to a buffer as they are read from the stream, and
+/// [`LengthPrefixCodec::decode`] takes the complete messages off the front of the buffer.
+/// A frame larger than the maximum frame size is reported once and then skipped,
+/// so the codec can carry on with the next frame.
+///
+/// With the `tokio` feature enabled, the codec implements `tokio_util::codec::Decoder` for `T`
+/// and `Encoder` for any serializable message, as [`LineCodec`](super::LineCodec) does.
+///
+/// ```rust
+/// use jsonrpc_types::{codec::LengthPrefixCodec, Request};
+///
+/// let mut codec = LengthPrefixCodec::<Request>::new();
+/// let mut buf = vec![0, 0, 0, 39];
+/// assert!(codec.decode(&mut buf).unwrap().is_none());
+///
+/// buf.extend_from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":1}"#);
+/// let request = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(request.to_string(), r#"{"jsonrpc":"2.0","method":"foo","id":1}"#);
+/// assert!(buf.is_empty());
+/// ```
+pub struct LengthPrefixCodec<T> {
+    max_frame_size: usize,
+    /// The number of bytes of an oversized frame which are still to be skipped.
+    discarding: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for LengthPrefixCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LengthPrefixCodec")
+            .field("max_frame_size", &self.max_frame_size)
+            .field("discarding", &self.discarding)
+            .finish()
+    }
+}
+
+impl<T> Clone for LengthPrefixCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_size: self.max_frame_size,
+            discarding: self.discarding,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LengthPrefixCodec<T> {
+    fn default() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
+impl<T> LengthPrefixCodec<T> {
+    /// Creates a codec with the default maximum frame size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a codec with the given maximum frame size in bytes, excluding the length prefix.
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            max_frame_size,
+            discarding: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the maximum frame size in bytes.
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Encodes the message as a compact frame prefixed with its length, appending it to `dst`.
+    pub fn encode<U: Serialize>(&mut self, item: &U, dst: &mut Vec<u8>) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(item)?);
+        Ok(())
+    }
+
+    /// Looks for the next frame at the front of the buffer.
+    ///
+    /// Returns the number of bytes that must be consumed from the buffer, along with the range
+    /// of the next message if it's complete.
+    pub(crate) fn split_frame(
+        &mut self,
+        buf: &[u8],
+    ) -> (usize, Result<Option<Range<usize>>, CodecError>) {
+        let start = self.discarding.min(buf.len());
+        self.discarding -= start;
+        if self.discarding > 0 {
+            return (start, Ok(None));
+        }
+
+        let rest = &buf[start..];
+        if rest.len() < PREFIX_SIZE {
+            return (start, Ok(None));
+        }
+        let mut prefix = [0; PREFIX_SIZE];
+        prefix.copy_from_slice(&rest[..PREFIX_SIZE]);
+        let len = u32::from_be_bytes(prefix) as usize;
+        if len > self.max_frame_size {
+            self.discarding = len;
+            return (start + PREFIX_SIZE, Err(self.too_large()));
+        }
+        if rest.len() < PREFIX_SIZE + len {
+            return (start, Ok(None));
+        }
+        let message = start + PREFIX_SIZE;
+        (message + len, Ok(Some(message..message + len)))
+    }
+
+    /// Serializes the message into a compact frame, including the length prefix.
+    pub(crate) fn to_frame<U: Serialize>(&self, item: &U) -> Result<Vec<u8>, CodecError> {
+        let message = serde_json::to_vec(item)?;
+        let len = match u32::try_from(message.len()) {
+            Ok(len) if message.len() <= self.max_frame_size => len,
+            _ => return Err(self.too_large()),
+        };
+        let mut frame = Vec::with_capacity(PREFIX_SIZE + message.len());
+        frame.extend_from_slice(&len.to_be_bytes());
+        frame.extend_from_slice(&message);
+        Ok(frame)
+    }
+
+    fn too_large(&self) -> CodecError {
+        CodecError::FrameTooLarge {
+            max: self.max_frame_size,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> LengthPrefixCodec<T> {
+    /// Decodes the next message at the front of the buffer, removing it from the buffer.
+    ///
+    /// Returns `None` if the buffer doesn't contain a complete frame yet.
+    pub fn decode(&mut self, buf: &mut Vec<u8>) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(buf);
+        let item = parse_frame(buf, frame);
+        buf.drain(..consumed);
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2;
+
+    fn frame(message: &[u8]) -> Vec<u8> {
+        [&(message.len() as u32).to_be_bytes()[..], message].concat()
+    }
+
+    #[test]
+    fn decode_partial_frames() {
+        let input = [
+            frame(br#"{"jsonrpc":"2.0","method":"foo","id":1}"#),
+            frame(br#"[{"jsonrpc":"2.0","method":"bar"}]"#),
+        ]
+        .concat();
+
+        // Feeds the input byte by byte.
+        let mut codec = LengthPrefixCodec::<v2::Request>::new();
+        let mut buf = Vec::new();
+        let mut requests = Vec::new();
+        for &b in &input {
+            buf.push(b);
+            while let Some(request) = codec.decode(&mut buf).unwrap() {
+                requests.push(request);
+            }
+        }
+        assert!(buf.is_empty());
+        assert_eq!(
+            requests,
+            vec![
+                v2::Request::Single(v2::MethodCall::new("foo", (), 1.into()).into()),
+                v2::Request::Batch(vec![v2::Notification::new("bar", ()).into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_oversized_frame() {
+        let mut codec = LengthPrefixCodec::<v2::Request>::with_max_frame_size(40);
+        let mut buf = frame(&[b'x'; 41][..30]);
+        buf[3] = 41;
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::FrameTooLarge { max: 40 })
+        ));
+
+        // The rest of the oversized frame is skipped.
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&[b'x'; 11]);
+        buf.extend_from_slice(&frame(br#"{"jsonrpc":"2.0","method":"foo"}"#));
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+        assert!(buf.is_empty());
+
+        let mut buf = frame(br#"{"foo":1}"#);
+        assert!(matches!(codec.decode(&mut buf), Err(CodecError::Json(_))));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn encode_frames() {
+        let mut codec = LengthPrefixCodec::<v2::Request>::with_max_frame_size(64);
+        let mut dst = Vec::new();
+        let output = v2::Output::success(v2::Value::Bool(true), 1.into());
+        codec.encode(&output, &mut dst).unwrap();
+        assert_eq!(dst, frame(br#"{"jsonrpc":"2.0","result":true,"id":1}"#));
+
+        let output = v2::Output::success(v2::Value::from("x".repeat(64)), 1.into());
+        assert!(matches!(
+            codec.encode(&output, &mut Vec::new()),
+            Err(CodecError::FrameTooLarge { max: 64 })
+        ));
+    }
+}
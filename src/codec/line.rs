@@ -0,0 +1,329 @@
+use std::{fmt, marker::PhantomData, mem, ops::Range};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{parse_frame, CodecError, DEFAULT_MAX_FRAME_SIZE};
+
+/// Splits a byte stream into newline-delimited JSON-RPC messages of type `T`.
+///
+/// Bytes are appended to a buffer as they are read from the stream, and [`LineCodec::decode`]
+/// takes the complete messages off the front of the buffer. Blank lines and trailing `\r`s are
+/// ignored. A line larger than the maximum frame size is reported once and then skipped,
+/// so the codec can carry on with the next line.
+///
+/// With the `tokio` feature enabled, the codec implements `tokio_util::codec::Decoder` for `T`
+/// and `Encoder` for any serializable message, e.g. a `Framed<_, LineCodec<Request>>` decodes
+/// requests and encodes responses. As with any decoder, a `Framed` stream yields `None` once after
+/// a decode error, polling it again carries on with the next line.
+///
+/// ```rust
+/// use jsonrpc_types::{codec::LineCodec, Request};
+///
+/// let mut codec = LineCodec::<Request>::new();
+/// let mut buf = br#"{"jsonrpc":"2.0","method":"foo","id":1}"#.to_vec();
+/// assert!(codec.decode(&mut buf).unwrap().is_none());
+///
+/// buf.extend_from_slice(b"\n");
+/// let request = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(request.to_string(), r#"{"jsonrpc":"2.0","method":"foo","id":1}"#);
+/// assert!(buf.is_empty());
+/// ```
+pub struct LineCodec<T> {
+    max_frame_size: usize,
+    /// The index of the buffer from which to look for the next newline.
+    next_index: usize,
+    /// Whether the rest of an oversized line is being skipped.
+    discarding: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for LineCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LineCodec")
+            .field("max_frame_size", &self.max_frame_size)
+            .field("next_index", &self.next_index)
+            .field("discarding", &self.discarding)
+            .finish()
+    }
+}
+
+impl<T> Clone for LineCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            max_frame_size: self.max_frame_size,
+            next_index: self.next_index,
+            discarding: self.discarding,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LineCodec<T> {
+    fn default() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
+impl<T> LineCodec<T> {
+    /// Creates a codec with the default maximum frame size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a codec with the given maximum frame size in bytes, excluding the newline.
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            max_frame_size,
+            next_index: 0,
+            discarding: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the maximum frame size in bytes.
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Encodes the message as a compact line terminated by a newline, appending it to `dst`.
+    ///
+    /// Serialized JSON never contains raw newlines, so the message always fits on one line.
+    pub fn encode<U: Serialize>(&mut self, item: &U, dst: &mut Vec<u8>) -> Result<(), CodecError> {
+        dst.extend_from_slice(&self.to_frame(item)?);
+        Ok(())
+    }
+
+    /// Looks for the next line at the front of the buffer.
+    ///
+    /// Returns the number of bytes that must be consumed from the buffer, along with the range
+    /// of the next line if it's complete.
+    pub(crate) fn split_frame(
+        &mut self,
+        buf: &[u8],
+    ) -> (usize, Result<Option<Range<usize>>, CodecError>) {
+        let mut start = 0;
+        let (consumed, result) = loop {
+            let newline = buf[self.next_index..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|pos| self.next_index + pos);
+            match newline {
+                Some(end) => {
+                    self.next_index = end + 1;
+                    if mem::take(&mut self.discarding) {
+                        start = end + 1;
+                        continue;
+                    }
+                    if end - start > self.max_frame_size {
+                        break (end + 1, Err(self.too_large()));
+                    }
+                    let line = trim(buf, start..end);
+                    if line.is_empty() {
+                        start = end + 1;
+                        continue;
+                    }
+                    break (end + 1, Ok(Some(line)));
+                }
+                None => {
+                    self.next_index = buf.len();
+                    if self.discarding {
+                        break (buf.len(), Ok(None));
+                    }
+                    if buf.len() - start > self.max_frame_size {
+                        self.discarding = true;
+                        break (buf.len(), Err(self.too_large()));
+                    }
+                    break (start, Ok(None));
+                }
+            }
+        };
+        self.next_index -= consumed;
+        (consumed, result)
+    }
+
+    /// Looks for the last line of the buffer, which is not terminated by a newline,
+    /// once the end of the stream has been reached.
+    pub(crate) fn split_last_frame(
+        &mut self,
+        buf: &[u8],
+    ) -> (usize, Result<Option<Range<usize>>, CodecError>) {
+        match self.split_frame(buf) {
+            (consumed, Ok(None)) => {
+                let rest = &buf[consumed..];
+                self.next_index = 0;
+                if mem::take(&mut self.discarding) {
+                    return (buf.len(), Ok(None));
+                }
+                let line = trim(rest, 0..rest.len());
+                let line = (consumed + line.start)..(consumed + line.end);
+                (buf.len(), Ok(Some(line).filter(|line| !line.is_empty())))
+            }
+            frame => frame,
+        }
+    }
+
+    /// Serializes the message into a compact line, including the trailing newline.
+    pub(crate) fn to_frame<U: Serialize>(&self, item: &U) -> Result<Vec<u8>, CodecError> {
+        let mut frame = serde_json::to_vec(item)?;
+        if frame.len() > self.max_frame_size {
+            return Err(self.too_large());
+        }
+        frame.push(b'\n');
+        Ok(frame)
+    }
+
+    fn too_large(&self) -> CodecError {
+        CodecError::FrameTooLarge {
+            max: self.max_frame_size,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> LineCodec<T> {
+    /// Decodes the next message at the front of the buffer, removing it from the buffer.
+    ///
+    /// Returns `None` if the buffer doesn't contain a complete line yet.
+    pub fn decode(&mut self, buf: &mut Vec<u8>) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_frame(buf);
+        let item = parse_frame(buf, frame);
+        buf.drain(..consumed);
+        item
+    }
+
+    /// Decodes the next message once the end of the stream has been reached,
+    /// so the last line doesn't need to be terminated by a newline.
+    pub fn decode_eof(&mut self, buf: &mut Vec<u8>) -> Result<Option<T>, CodecError> {
+        let (consumed, frame) = self.split_last_frame(buf);
+        let item = parse_frame(buf, frame);
+        buf.drain(..consumed);
+        item
+    }
+}
+
+/// Trims the ASCII whitespaces of the line, including the `\r` of a `\r\n` line ending.
+fn trim(buf: &[u8], line: Range<usize>) -> Range<usize> {
+    let bytes = &buf[line.clone()];
+    match bytes.iter().position(|b| !b.is_ascii_whitespace()) {
+        Some(start) => {
+            let end = bytes
+                .iter()
+                .rposition(|b| !b.is_ascii_whitespace())
+                .unwrap_or(start);
+            (line.start + start)..(line.start + end + 1)
+        }
+        None => line.end..line.end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{v1, v2};
+
+    #[test]
+    fn decode_partial_lines() {
+        let mut codec = LineCodec::<v2::Request>::new();
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","method":"foo","id":1}"#,
+            "\r\n\n",
+            r#"[{"jsonrpc":"2.0","method":"bar"}]"#,
+            "\n",
+        );
+
+        // Feeds the input byte by byte.
+        let mut buf = Vec::new();
+        let mut requests = Vec::new();
+        for b in input.bytes() {
+            buf.push(b);
+            while let Some(request) = codec.decode(&mut buf).unwrap() {
+                requests.push(request);
+            }
+        }
+        assert!(buf.is_empty());
+        assert_eq!(
+            requests,
+            vec![
+                v2::Request::Single(v2::MethodCall::new("foo", (), 1.into()).into()),
+                v2::Request::Batch(vec![v2::Notification::new("bar", ()).into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_v1_and_eof() {
+        let mut codec = LineCodec::<v1::Response>::new();
+        let mut buf = br#"{"result":true,"error":null,"id":1}"#.to_vec();
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert_eq!(
+            codec.decode_eof(&mut buf).unwrap(),
+            Some(v1::Response::Single(v1::Output::success(
+                true.into(),
+                1.into()
+            )))
+        );
+        assert!(buf.is_empty());
+        assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_invalid_message() {
+        let mut codec = LineCodec::<v2::Request>::new();
+        let mut buf = b"{\"foo\":1}\n{\"jsonrpc\":\"2.0\",\"method\":\"foo\"}\n".to_vec();
+        assert!(matches!(codec.decode(&mut buf), Err(CodecError::Json(_))));
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+    }
+
+    #[test]
+    fn decode_oversized_line() {
+        let mut codec = LineCodec::<v2::Request>::with_max_frame_size(40);
+        let mut buf = vec![b' '; 20];
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        buf.extend_from_slice(&[b'x'; 30]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::FrameTooLarge { max: 40 })
+        ));
+        assert!(buf.is_empty());
+
+        // The rest of the oversized line is skipped.
+        buf.extend_from_slice(b"xxxxx\n{\"jsonrpc\":\"2.0\",\"method\":\"foo\"}\n");
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+
+        let mut buf = [
+            &[b'x'; 41][..],
+            b"\n{\"jsonrpc\":\"2.0\",\"method\":\"foo\"}\n",
+        ]
+        .concat();
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(CodecError::FrameTooLarge { max: 40 })
+        ));
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+    }
+
+    #[test]
+    fn encode_compact_lines() {
+        let mut codec = LineCodec::<v2::Request>::with_max_frame_size(64);
+        let mut dst = Vec::new();
+        let output = v2::Output::success(v2::Value::from("multi\nline"), 1.into());
+        codec.encode(&output, &mut dst).unwrap();
+        codec
+            .encode(&v1::Notification::new("foo", vec![]), &mut dst)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(dst).unwrap(),
+            concat!(
+                r#"{"jsonrpc":"2.0","result":"multi\nline","id":1}"#,
+                "\n",
+                r#"{"method":"foo","params":[],"id":null}"#,
+                "\n"
+            )
+        );
+
+        let output = v2::Output::success(v2::Value::from("x".repeat(64)), 1.into());
+        assert!(matches!(
+            codec.encode(&output, &mut Vec::new()),
+            Err(CodecError::FrameTooLarge { max: 64 })
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+/// tokio-util `Decoder`/`Encoder` implementations
+#[cfg(feature = "tokio")]
+mod framed;
+/// `Content-Length` header framing
+mod header;
+/// Binary length-prefixed framing
+mod length;
+/// Newline-delimited framing
+mod line;
+
+pub use self::{
+    header::{ContentLengthCodec, DEFAULT_CONTENT_TYPE, DEFAULT_MAX_HEADER_SIZE},
+    length::LengthPrefixCodec,
+    line::LineCodec,
+};
+
+use std::{error, fmt, io, ops::Range};
+
+use serde::de::DeserializeOwned;
+
+/// The default maximum size in bytes of a frame, i.e. 8 MiB.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// Represents the reason why a frame could not be decoded or encoded.
+#[derive(Debug)]
+pub enum CodecError {
+    /// The frame is larger than the maximum frame size.
+    FrameTooLarge {
+        /// The maximum frame size in bytes.
+        max: usize,
+    },
+    /// The header block of the frame is invalid.
+    Header(HeaderError),
+    /// The payload of the frame is not a valid JSON-RPC message.
+    Json(serde_json::Error),
+    /// An I/O error of the underlying stream.
+    Io(io::Error),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrameTooLarge { max } => write!(f, "Frame is larger than {} bytes", max),
+            Self::Header(err) => write!(f, "Invalid header: {}", err),
+            Self::Json(err) => write!(f, "Invalid JSON-RPC message: {}", err),
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Header(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<HeaderError> for CodecError {
+    fn from(err: HeaderError) -> Self {
+        Self::Header(err)
+    }
+}
+
+impl From<serde_json::Error> for CodecError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<io::Error> for CodecError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Represents the reason why a header block is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HeaderError {
+    /// The header block is larger than the maximum header size.
+    TooLarge {
+        /// The maximum header size in bytes.
+        max: usize,
+    },
+    /// A header field is not of the form `name: value`, or is not valid UTF-8.
+    Malformed(String),
+    /// The `Content-Length` header is missing.
+    MissingContentLength,
+    /// The `Content-Length` header is given more than once.
+    DuplicateContentLength,
+    /// The value of the `Content-Length` header is not a valid length.
+    InvalidContentLength(String),
+    /// The charset of the `Content-Type` header is not UTF-8.
+    UnsupportedCharset(String),
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { max } => write!(f, "Header is larger than {} bytes", max),
+            Self::Malformed(field) => write!(f, "Malformed header field `{}`", field),
+            Self::MissingContentLength => f.write_str("Missing `Content-Length` header"),
+            Self::DuplicateContentLength => f.write_str("Duplicate `Content-Length` header"),
+            Self::InvalidContentLength(value) => write!(f, "Invalid content length `{}`", value),
+            Self::UnsupportedCharset(charset) => write!(f, "Unsupported charset `{}`", charset),
+        }
+    }
+}
+
+impl error::Error for HeaderError {}
+
+/// Deserializes the message of a frame.
+pub(crate) fn parse_frame<T: DeserializeOwned>(
+    buf: &[u8],
+    frame: Result<Option<Range<usize>>, CodecError>,
+) -> Result<Option<T>, CodecError> {
+    match frame? {
+        Some(range) => Ok(Some(serde_json::from_slice(&buf[range])?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,537 @@
+use std::{
+    convert::TryFrom,
+    error, fmt,
+    io::{self, Write},
+};
+
+use serde::Serialize;
+
+use crate::{
+    v1, v1_1,
+    v2::{self, Version},
+};
+
+/// Represents the reason why a message cannot be converted between JSON-RPC 1.0 and 2.0
+/// without losing information.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConversionError {
+    /// The parameters are named, while JSON-RPC 1.0 only supports positional parameters.
+    NamedParams,
+    /// The id of the method call is Null, which denotes a notification in JSON-RPC 1.0.
+    NullMethodCallId,
+    /// The output carries both a result and an error.
+    ResultAndError,
+    /// The output carries neither a result nor an error.
+    NoResultOrError,
+    /// The result of the success output is Null, which denotes a failure in JSON-RPC 1.0.
+    NullResult,
+    /// The id of the success output is Null, which JSON-RPC 1.0 only allows for failures.
+    NullSuccessId,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NamedParams => f.write_str("JSON-RPC 1.0 doesn't support named parameters"),
+            Self::NullMethodCallId => f.write_str("JSON-RPC 1.0 method call id MUST NOT be Null"),
+            Self::ResultAndError => f.write_str("Output has both a result and an error"),
+            Self::NoResultOrError => f.write_str("Output has neither a result nor an error"),
+            Self::NullResult => f.write_str("JSON-RPC 1.0 success result MUST NOT be Null"),
+            Self::NullSuccessId => f.write_str("JSON-RPC 1.0 success id MUST NOT be Null"),
+        }
+    }
+}
+
+impl error::Error for ConversionError {}
+
+fn v2_params_into_v1(params: Option<v2::Params>) -> Result<v1::Params, ConversionError> {
+    match params {
+        None => Ok(v1::Params::new()),
+        Some(v2::Params::Array(params)) => Ok(params),
+        Some(v2::Params::Map(_)) => Err(ConversionError::NamedParams),
+    }
+}
+
+/// The positional parameters of the JSON-RPC 1.0 method call are kept as an array, even if empty.
+impl From<v1::MethodCall> for v2::MethodCall {
+    fn from(call: v1::MethodCall) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: call.method,
+            params: Some(v2::Params::Array(call.params)),
+            id: call.id,
+        }
+    }
+}
+
+/// Omitted parameters become an empty array, named parameters and a Null id are rejected.
+impl TryFrom<v2::MethodCall> for v1::MethodCall {
+    type Error = ConversionError;
+
+    fn try_from(call: v2::MethodCall) -> Result<Self, Self::Error> {
+        if call.id.is_null() {
+            return Err(ConversionError::NullMethodCallId);
+        }
+        Ok(Self {
+            method: call.method,
+            params: v2_params_into_v1(call.params)?,
+            id: call.id,
+        })
+    }
+}
+
+impl From<v1::Notification> for v2::Notification {
+    fn from(notification: v1::Notification) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: notification.method,
+            params: Some(v2::Params::Array(notification.params)),
+        }
+    }
+}
+
+impl TryFrom<v2::Notification> for v1::Notification {
+    type Error = ConversionError;
+
+    fn try_from(notification: v2::Notification) -> Result<Self, Self::Error> {
+        Ok(Self {
+            method: notification.method,
+            params: v2_params_into_v1(notification.params)?,
+        })
+    }
+}
+
+impl From<v1::Call> for v2::Call {
+    fn from(call: v1::Call) -> Self {
+        match call {
+            v1::Call::MethodCall(call) => Self::MethodCall(call.into()),
+            v1::Call::Notification(notification) => Self::Notification(notification.into()),
+        }
+    }
+}
+
+impl TryFrom<v2::Call> for v1::Call {
+    type Error = ConversionError;
+
+    fn try_from(call: v2::Call) -> Result<Self, Self::Error> {
+        match call {
+            v2::Call::MethodCall(call) => v1::MethodCall::try_from(call).map(Self::MethodCall),
+            v2::Call::Notification(notification) => {
+                v1::Notification::try_from(notification).map(Self::Notification)
+            }
+        }
+    }
+}
+
+impl From<v1::Request> for v2::Request {
+    fn from(request: v1::Request) -> Self {
+        match request {
+            v1::Request::Single(call) => Self::Single(call.into()),
+            v1::Request::Batch(calls) => Self::Batch(calls.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl TryFrom<v2::Request> for v1::Request {
+    type Error = ConversionError;
+
+    fn try_from(request: v2::Request) -> Result<Self, Self::Error> {
+        match request {
+            v2::Request::Single(call) => v1::Call::try_from(call).map(Self::Single),
+            v2::Request::Batch(calls) => calls
+                .into_iter()
+                .map(v1::Call::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::Batch),
+        }
+    }
+}
+
+impl From<v1_1::MethodCall> for v2::MethodCall {
+    fn from(call: v1_1::MethodCall) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: call.method,
+            params: call.params,
+            id: call.id,
+        }
+    }
+}
+
+impl From<v2::MethodCall> for v1_1::MethodCall {
+    fn from(call: v2::MethodCall) -> Self {
+        Self {
+            version: v1_1::Version::V1_1,
+            method: call.method,
+            params: call.params,
+            id: call.id,
+        }
+    }
+}
+
+impl From<v1_1::Notification> for v2::Notification {
+    fn from(notification: v1_1::Notification) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: notification.method,
+            params: notification.params,
+        }
+    }
+}
+
+impl From<v2::Notification> for v1_1::Notification {
+    fn from(notification: v2::Notification) -> Self {
+        Self {
+            version: v1_1::Version::V1_1,
+            method: notification.method,
+            params: notification.params,
+        }
+    }
+}
+
+impl From<v1_1::Call> for v2::Call {
+    fn from(call: v1_1::Call) -> Self {
+        match call {
+            v1_1::Call::MethodCall(call) => Self::MethodCall(call.into()),
+            v1_1::Call::Notification(notification) => Self::Notification(notification.into()),
+        }
+    }
+}
+
+impl From<v2::Call> for v1_1::Call {
+    fn from(call: v2::Call) -> Self {
+        match call {
+            v2::Call::MethodCall(call) => Self::MethodCall(call.into()),
+            v2::Call::Notification(notification) => Self::Notification(notification.into()),
+        }
+    }
+}
+
+impl From<v1_1::Request> for v2::Request {
+    fn from(request: v1_1::Request) -> Self {
+        match request {
+            v1_1::Request::Single(call) => Self::Single(call.into()),
+            v1_1::Request::Batch(calls) => Self::Batch(calls.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<v2::Request> for v1_1::Request {
+    fn from(request: v2::Request) -> Self {
+        match request {
+            v2::Request::Single(call) => Self::Single(call.into()),
+            v2::Request::Batch(calls) => Self::Batch(calls.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl<T> TryFrom<v1::Output<T>> for v2::Output<T> {
+    type Error = ConversionError;
+
+    fn try_from(output: v1::Output<T>) -> Result<Self, Self::Error> {
+        match (output.result, output.error) {
+            (Some(result), None) => Ok(Self::Success(v2::Success {
+                jsonrpc: Version::V2_0,
+                result,
+                id: output.id,
+            })),
+            (None, Some(error)) => Ok(Self::Failure(v2::Failure {
+                jsonrpc: Version::V2_0,
+                error,
+                id: output.id,
+            })),
+            (Some(_), Some(_)) => Err(ConversionError::ResultAndError),
+            (None, None) => Err(ConversionError::NoResultOrError),
+        }
+    }
+}
+
+/// Whether the value serializes to `null`, without serializing more than a few bytes of it.
+fn is_null<T: Serialize>(value: &T) -> bool {
+    const NULL: &[u8] = b"null";
+
+    struct Prefix(Vec<u8>);
+    impl Write for Prefix {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0.len() + buf.len() > NULL.len() {
+                return Err(io::ErrorKind::Other.into());
+            }
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut prefix = Prefix(Vec::with_capacity(NULL.len()));
+    serde_json::to_writer(&mut prefix, value).is_ok() && prefix.0 == NULL
+}
+
+/// A success with a Null result or a Null id is rejected, since JSON-RPC 1.0 doesn't
+/// consider it a success.
+impl<T: Serialize> TryFrom<v2::Output<T>> for v1::Output<T> {
+    type Error = ConversionError;
+
+    fn try_from(output: v2::Output<T>) -> Result<Self, Self::Error> {
+        match output {
+            v2::Output::Success(success) => {
+                if is_null(&success.result) {
+                    return Err(ConversionError::NullResult);
+                }
+                if success.id.is_null() {
+                    return Err(ConversionError::NullSuccessId);
+                }
+                Ok(Self {
+                    result: Some(success.result),
+                    error: None,
+                    id: success.id,
+                })
+            }
+            v2::Output::Failure(failure) => Ok(Self {
+                result: None,
+                error: Some(failure.error),
+                id: failure.id,
+            }),
+        }
+    }
+}
+
+impl<T> TryFrom<v1::Response<T>> for v2::Response<T> {
+    type Error = ConversionError;
+
+    fn try_from(response: v1::Response<T>) -> Result<Self, Self::Error> {
+        match response {
+            v1::Response::Single(output) => v2::Output::try_from(output).map(Self::Single),
+            v1::Response::Batch(outputs) => outputs
+                .into_iter()
+                .map(v2::Output::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::Batch),
+        }
+    }
+}
+
+impl<T: Serialize> TryFrom<v2::Response<T>> for v1::Response<T> {
+    type Error = ConversionError;
+
+    fn try_from(response: v2::Response<T>) -> Result<Self, Self::Error> {
+        match response {
+            v2::Response::Single(output) => v1::Output::try_from(output).map(Self::Single),
+            v2::Response::Batch(outputs) => outputs
+                .into_iter()
+                .map(v1::Output::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::Batch),
+        }
+    }
+}
+
+/// The name of the JSON-RPC 1.1 error is dropped, since it is always `JSONRPCError`.
+impl<T> From<v1_1::Output<T>> for v2::Output<T> {
+    fn from(output: v1_1::Output<T>) -> Self {
+        match output {
+            v1_1::Output::Success(success) => Self::Success(v2::Success {
+                jsonrpc: Version::V2_0,
+                result: success.result,
+                id: success.id,
+            }),
+            v1_1::Output::Failure(failure) => Self::Failure(v2::Failure {
+                jsonrpc: Version::V2_0,
+                error: failure.error.into(),
+                id: failure.id,
+            }),
+        }
+    }
+}
+
+impl<T> From<v2::Output<T>> for v1_1::Output<T> {
+    fn from(output: v2::Output<T>) -> Self {
+        match output {
+            v2::Output::Success(success) => Self::Success(v1_1::Success {
+                version: v1_1::Version::V1_1,
+                result: success.result,
+                id: success.id,
+            }),
+            v2::Output::Failure(failure) => Self::Failure(v1_1::Failure {
+                version: v1_1::Version::V1_1,
+                error: failure.error.into(),
+                id: failure.id,
+            }),
+        }
+    }
+}
+
+impl<T> From<v1_1::Response<T>> for v2::Response<T> {
+    fn from(response: v1_1::Response<T>) -> Self {
+        match response {
+            v1_1::Response::Single(output) => Self::Single(output.into()),
+            v1_1::Response::Batch(outputs) => {
+                Self::Batch(outputs.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+impl<T> From<v2::Response<T>> for v1_1::Response<T> {
+    fn from(response: v2::Response<T>) -> Self {
+        match response {
+            v2::Response::Single(output) => Self::Single(output.into()),
+            v2::Response::Batch(outputs) => {
+                Self::Batch(outputs.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Map, Value};
+
+    use super::*;
+    use crate::{error::Error, id::Id};
+
+    #[test]
+    fn request_conversion() {
+        let call = v1::MethodCall::new("foo", vec![Value::from(1)], 1.into());
+        let converted = v2::MethodCall::from(call.clone());
+        assert_eq!(
+            converted.to_string(),
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1],"id":1}"#
+        );
+        assert_eq!(v1::MethodCall::try_from(converted), Ok(call));
+
+        let notification = v1::Notification::new("foo", vec![]);
+        let converted = v2::Call::from(v1::Call::Notification(notification.clone()));
+        assert_eq!(
+            converted.to_string(),
+            r#"{"jsonrpc":"2.0","method":"foo","params":[]}"#
+        );
+        assert_eq!(
+            v1::Call::try_from(converted),
+            Ok(v1::Call::Notification(notification))
+        );
+
+        // Omitted parameters are an empty array in JSON-RPC 1.0.
+        let call = v2::MethodCall::new("foo", (), 1.into());
+        assert_eq!(
+            v1::MethodCall::try_from(call).unwrap().to_string(),
+            r#"{"method":"foo","params":[],"id":1}"#
+        );
+
+        let request = v2::Request::Batch(vec![
+            v2::MethodCall::new("foo", (), 1.into()).into(),
+            v2::Notification::new("bar", Some(v2::Params::Map(Map::new()))).into(),
+        ]);
+        assert_eq!(
+            v1::Request::try_from(request),
+            Err(ConversionError::NamedParams)
+        );
+
+        let call = v2::MethodCall::new("foo", (), Id::Null);
+        assert_eq!(
+            v1::MethodCall::try_from(call),
+            Err(ConversionError::NullMethodCallId)
+        );
+    }
+
+    #[test]
+    fn response_conversion() {
+        let response = v1::Response::Batch(vec![
+            v1::Output::success(Value::Bool(true), 1.into()),
+            v1::Output::failure(Error::method_not_found(), 2.into()),
+        ]);
+        let converted = v2::Response::try_from(response.clone()).unwrap();
+        assert_eq!(
+            converted.to_string(),
+            r#"[{"jsonrpc":"2.0","result":true,"id":1},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}]"#
+        );
+        assert_eq!(v1::Response::try_from(converted), Ok(response));
+
+        let output = v1::Output {
+            result: Some(Value::Bool(true)),
+            error: Some(Error::internal_error()),
+            id: 1.into(),
+        };
+        assert_eq!(
+            v2::Output::try_from(output),
+            Err(ConversionError::ResultAndError)
+        );
+
+        let output = v1::Output::<Value> {
+            result: None,
+            error: None,
+            id: 1.into(),
+        };
+        assert_eq!(
+            v2::Output::try_from(output),
+            Err(ConversionError::NoResultOrError)
+        );
+    }
+
+    #[test]
+    fn v1_output_round_trip() {
+        let outputs = vec![
+            v2::Output::success(Value::from("x".repeat(16)), 1.into()),
+            v2::Output::success(Value::Bool(false), 1.into()),
+            v2::Output::failure(Error::parse_error(), Id::Null),
+        ];
+        for output in outputs {
+            let converted = v1::Output::try_from(output.clone()).unwrap();
+            let decoded: v1::Output = serde_json::from_str(&converted.to_string()).unwrap();
+            assert_eq!(v2::Output::try_from(decoded), Ok(output));
+        }
+
+        // The v1 decoder would reject these outputs.
+        let output = v2::Output::success(Value::Null, 1.into());
+        assert_eq!(
+            v1::Output::try_from(output),
+            Err(ConversionError::NullResult)
+        );
+        let output = v2::Output::success(Option::<u64>::None, 1.into());
+        assert_eq!(
+            v1::Output::try_from(output),
+            Err(ConversionError::NullResult)
+        );
+        let output = v2::Output::success(Value::Bool(true), Id::Null);
+        assert_eq!(
+            v1::Output::try_from(output),
+            Err(ConversionError::NullSuccessId)
+        );
+
+        let response = v2::Response::Batch(vec![
+            v2::Output::success(Value::Bool(true), 1.into()),
+            v2::Output::success(Value::Null, 2.into()),
+        ]);
+        assert_eq!(
+            v1::Response::try_from(response),
+            Err(ConversionError::NullResult)
+        );
+    }
+
+    #[test]
+    fn v1_1_conversion() {
+        let request: v1_1::Request = serde_json::from_str(
+            r#"[{"version":"1.1","method":"foo","params":{"name":"foo"},"id":1},{"version":"1.1","method":"bar"}]"#,
+        )
+        .unwrap();
+        let converted = v2::Request::from(request.clone());
+        assert_eq!(
+            converted.to_string(),
+            r#"[{"jsonrpc":"2.0","method":"foo","params":{"name":"foo"},"id":1},{"jsonrpc":"2.0","method":"bar"}]"#
+        );
+        assert_eq!(v1_1::Request::from(converted), request);
+
+        let response = v2::Response::Batch(vec![
+            v2::Output::success(Value::Bool(true), 1.into()),
+            v2::Output::failure(Error::method_not_found(), Id::Null),
+        ]);
+        let converted = v1_1::Response::from(response.clone());
+        assert_eq!(
+            converted.to_string(),
+            r#"[{"version":"1.1","result":true,"id":1},{"version":"1.1","error":{"name":"JSONRPCError","code":-32601,"message":"Method not found"},"id":null}]"#
+        );
+        assert_eq!(v2::Response::from(converted), response);
+    }
+}
@@ -1,7 +1,7 @@
-use std::{error, fmt};
+use std::{collections::HashMap, error, fmt, ops::RangeInclusive, sync::Arc};
 
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::Value;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{error::Category, Value};
 
 /// JSON-RPC Error Code.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -21,6 +21,11 @@ pub enum ErrorCode {
     ServerError(i64),
 }
 
+/// The range of error codes reserved for pre-defined errors.
+const RESERVED_RANGE: RangeInclusive<i64> = -32768..=-32000;
+/// The range of error codes reserved for implementation-defined server errors.
+const SERVER_ERROR_RANGE: RangeInclusive<i64> = -32099..=-32000;
+
 impl From<i64> for ErrorCode {
     fn from(code: i64) -> Self {
         match code {
@@ -67,6 +72,8 @@ impl ErrorCode {
     }
 
     /// Returns human-readable description.
+    ///
+    /// See [`ErrorRegistry`] for describing server and application errors by name.
     pub fn description(&self) -> String {
         let desc = match self {
             ErrorCode::ParseError => "Parse error",
@@ -78,12 +85,133 @@ impl ErrorCode {
         };
         desc.to_string()
     }
+
+    /// Creates an implementation-defined server error code, which MUST be in the range
+    /// -32099 to -32000.
+    pub fn server_error(code: i64) -> Result<Self, InvalidErrorCode> {
+        if SERVER_ERROR_RANGE.contains(&code) {
+            Ok(ErrorCode::ServerError(code))
+        } else {
+            Err(InvalidErrorCode(code))
+        }
+    }
+
+    /// Creates an application-defined error code, which MUST NOT be in the reserved range
+    /// -32768 to -32000.
+    pub fn application_defined(code: i64) -> Result<Self, InvalidErrorCode> {
+        if RESERVED_RANGE.contains(&code) {
+            Err(InvalidErrorCode(code))
+        } else {
+            Ok(ErrorCode::ServerError(code))
+        }
+    }
+
+    /// Returns `true` if the code is in the range -32768 to -32000 reserved by the specification,
+    /// including the pre-defined errors and the server errors.
+    pub fn is_reserved(&self) -> bool {
+        RESERVED_RANGE.contains(&self.code())
+    }
+
+    /// Returns `true` if the code is in the range -32099 to -32000 reserved for
+    /// implementation-defined server errors.
+    pub fn is_server_error_range(&self) -> bool {
+        SERVER_ERROR_RANGE.contains(&self.code())
+    }
+
+    /// Returns `true` if the code is outside of the reserved range, i.e. defined by the application.
+    pub fn is_application_defined(&self) -> bool {
+        !self.is_reserved()
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.description(), self.code())
+    }
 }
 
+/// Names of server and application error codes, which describe them instead of the generic
+/// `Server error` description.
+///
+/// ```rust
+/// use jsonrpc_types::{ErrorCode, ErrorRegistry};
+///
+/// let mut registry = ErrorRegistry::new();
+/// registry.register(-32001, "Unknown block").unwrap();
+/// let error = registry.error(ErrorCode::from(-32001));
+/// assert_eq!(error.message, "Unknown block");
+///
+/// assert!(registry.register(-32601, "Unknown method").is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ErrorRegistry {
+    names: HashMap<i64, String>,
+}
+
+impl ErrorRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the name of a server or application error code.
+    ///
+    /// Registering a code again replaces its name.
+    /// Pre-defined codes and the other reserved codes cannot be registered.
+    pub fn register<N: Into<String>>(
+        &mut self,
+        code: i64,
+        name: N,
+    ) -> Result<(), InvalidErrorCode> {
+        if RESERVED_RANGE.contains(&code) && !SERVER_ERROR_RANGE.contains(&code) {
+            return Err(InvalidErrorCode(code));
+        }
+        self.names.insert(code, name.into());
+        Ok(())
+    }
+
+    /// Returns the registered name of the code, if any.
+    pub fn name(&self, code: ErrorCode) -> Option<&str> {
+        self.names.get(&code.code()).map(String::as_str)
+    }
+
+    /// Returns the registered name of the code, or its human-readable description.
+    pub fn description(&self, code: ErrorCode) -> String {
+        match self.name(code) {
+            Some(name) => name.to_owned(),
+            None => code.description(),
+        }
+    }
+
+    /// Creates an error whose message is the description of the code.
+    pub fn error(&self, code: ErrorCode) -> Error {
+        let mut error = Error::new(code);
+        error.message = self.description(code);
+        error
+    }
+}
+
+/// Represents an error code which is out of the expected range.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InvalidErrorCode(pub i64);
+
+impl fmt::Display for InvalidErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Error code {} is out of the expected range", self.0)
+    }
+}
+
+impl error::Error for InvalidErrorCode {}
+
 /// JSON-RPC Error Object.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// The additional information of the error is of type `D`, which defaults to a JSON [`Value`].
+///
+/// The error may also carry its underlying cause, e.g. for server-side logging.
+/// The cause is never serialized, see [`CausePolicy`] for exposing it in the `data` member.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct Error {
+pub struct Error<D = Value> {
     /// A Number that indicates the error type that occurred.
     /// This MUST be an integer.
     pub code: ErrorCode,
@@ -94,26 +222,117 @@ pub struct Error {
     /// This may be omitted.
     /// The value of this member is defined by the Server (e.g. detailed error information, nested errors etc.).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<Value>,
+    pub data: Option<D>,
+    /// The underlying cause of the error, which is only available locally.
+    #[serde(skip)]
+    cause: Option<Arc<dyn error::Error + Send + Sync>>,
 }
 
-impl fmt::Display for Error {
+/// The cause is ignored, since it isn't a part of the error object.
+impl<D: PartialEq> PartialEq for Error<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.message == other.message && self.data == other.data
+    }
+}
+
+impl<D: Eq> Eq for Error<D> {}
+
+impl<D> fmt::Display for Error<D> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}: {}", self.code.description(), self.message)
     }
 }
 
-impl error::Error for Error {}
+impl<D: fmt::Debug> error::Error for Error<D> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.cause {
+            Some(cause) => Some(cause.as_ref()),
+            None => None,
+        }
+    }
+}
 
-impl Error {
-    /// Wraps given `ErrorCode`.
-    pub fn new(code: ErrorCode) -> Self {
+impl<D> Error<D> {
+    /// Creates an error with the given message and additional information.
+    ///
+    /// ```rust
+    /// use jsonrpc_types::{Error, ErrorCode};
+    ///
+    /// let error = Error::with_data(ErrorCode::ServerError(-32001), "Unknown block", Some(7));
+    /// assert_eq!(
+    ///     serde_json::to_string(&error).unwrap(),
+    ///     r#"{"code":-32001,"message":"Unknown block","data":7}"#
+    /// );
+    /// ```
+    pub fn with_data<M: Into<String>>(code: ErrorCode, message: M, data: Option<D>) -> Self {
         Error {
-            message: code.description(),
             code,
-            data: None,
+            message: message.into(),
+            data,
+            cause: None,
+        }
+    }
+
+    /// Attaches the underlying cause of the error.
+    pub fn with_cause<E>(mut self, cause: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.cause = Some(Arc::new(cause));
+        self
+    }
+
+    /// Converts the additional information of the error with the given function.
+    pub fn map_data<E, F>(self, f: F) -> Error<E>
+    where
+        F: FnOnce(D) -> E,
+    {
+        Error {
+            code: self.code,
+            message: self.message,
+            data: self.data.map(f),
+            cause: self.cause,
         }
     }
+}
+
+impl<D: Serialize> Error<D> {
+    /// Converts the additional information of the error into `E` through its JSON representation.
+    ///
+    /// ```rust
+    /// use jsonrpc_types::{Error, Value};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Details {
+    ///     field: String,
+    /// }
+    ///
+    /// let error: Error = serde_json::from_str(
+    ///     r#"{"code":-32602,"message":"Invalid params","data":{"field":"foo"}}"#
+    /// ).unwrap();
+    /// let error = error.try_data_into::<Details>().unwrap();
+    /// assert_eq!(error.data, Some(Details { field: "foo".into() }));
+    /// ```
+    pub fn try_data_into<E: DeserializeOwned>(self) -> Result<Error<E>, serde_json::Error> {
+        let data = match self.data {
+            Some(data) => Some(serde_json::from_value(serde_json::to_value(data)?)?),
+            None => None,
+        };
+        Ok(Error {
+            code: self.code,
+            message: self.message,
+            data,
+            cause: self.cause,
+        })
+    }
+}
+
+impl Error {
+    /// Wraps given `ErrorCode`.
+    pub fn new(code: ErrorCode) -> Self {
+        Self::with_data(code, code.description(), None)
+    }
 
     /// Creates a new `ParseError` error.
     pub fn parse_error() -> Self {
@@ -135,11 +354,11 @@ impl Error {
     where
         M: fmt::Display,
     {
-        Error {
-            code: ErrorCode::InvalidParams,
-            message: format!("Invalid parameters: {}", message),
-            data: None,
-        }
+        Self::with_data(
+            ErrorCode::InvalidParams,
+            format!("Invalid parameters: {}", message),
+            None,
+        )
     }
 
     /// Creates a new `InvalidParams` error with given message and details.
@@ -148,11 +367,11 @@ impl Error {
         M: fmt::Display,
         D: fmt::Display,
     {
-        Error {
-            code: ErrorCode::InvalidParams,
-            message: format!("Invalid parameters: {}", message),
-            data: Some(Value::String(details.to_string())),
-        }
+        Self::with_data(
+            ErrorCode::InvalidParams,
+            format!("Invalid parameters: {}", message),
+            Some(Value::String(details.to_string())),
+        )
     }
 
     /// Creates a new `InternalError` error.
@@ -162,11 +381,137 @@ impl Error {
 
     /// Creates a new `InvalidRequest` error with invalid version description.
     pub fn invalid_version() -> Self {
-        Error {
-            code: ErrorCode::InvalidRequest,
-            message: "Unsupported JSON-RPC protocol version".to_owned(),
-            data: None,
+        Self::with_data(
+            ErrorCode::InvalidRequest,
+            "Unsupported JSON-RPC protocol version",
+            None,
+        )
+    }
+
+    /// Applies the policy to the cause of the error before it is sent to the peer.
+    ///
+    /// The `data` member is only filled from the cause if it is omitted.
+    ///
+    /// ```rust
+    /// use jsonrpc_types::{Error, ExposeCause, Value};
+    ///
+    /// let err = serde_json::from_str::<Value>("{").unwrap_err();
+    /// let error = Error::parse_error().with_cause(err);
+    /// assert_eq!(
+    ///     error.apply_cause_policy(&ExposeCause).data,
+    ///     Some(Value::from("EOF while parsing an object at line 1 column 1"))
+    /// );
+    /// ```
+    pub fn apply_cause_policy<P: CausePolicy + ?Sized>(mut self, policy: &P) -> Self {
+        if self.data.is_none() {
+            if let Some(cause) = &self.cause {
+                self.data = policy.expose(&self, cause.as_ref());
+            }
         }
+        self
+    }
+}
+
+/// Decides whether the underlying cause of an error is exposed to the peer in the `data` member.
+///
+/// Functions taking the error and its cause are policies as well.
+/// The server routers apply their policy to the errors of the handlers, see
+/// `Router::cause_policy`.
+pub trait CausePolicy {
+    /// Returns the data exposing the cause, or `None` if the cause is redacted.
+    fn expose(
+        &self,
+        error: &Error,
+        cause: &(dyn error::Error + Send + Sync + 'static),
+    ) -> Option<Value>;
+}
+
+impl<F> CausePolicy for F
+where
+    F: Fn(&Error, &(dyn error::Error + Send + Sync + 'static)) -> Option<Value>,
+{
+    fn expose(
+        &self,
+        error: &Error,
+        cause: &(dyn error::Error + Send + Sync + 'static),
+    ) -> Option<Value> {
+        self(error, cause)
+    }
+}
+
+/// The policy which redacts the cause of errors.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RedactCause;
+
+impl CausePolicy for RedactCause {
+    fn expose(
+        &self,
+        _error: &Error,
+        _cause: &(dyn error::Error + Send + Sync + 'static),
+    ) -> Option<Value> {
+        None
+    }
+}
+
+/// The policy which exposes the message of the cause of errors, e.g. for debugging.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExposeCause;
+
+impl CausePolicy for ExposeCause {
+    fn expose(
+        &self,
+        _error: &Error,
+        cause: &(dyn error::Error + Send + Sync + 'static),
+    ) -> Option<Value> {
+        Some(Value::String(cause.to_string()))
+    }
+}
+
+/// Conversion of an application error into a JSON-RPC error object.
+///
+/// With the `derive` feature enabled, `#[derive(IntoRpcError)]` converts each variant of an enum
+/// annotated with `#[rpc_error(code = -32001, message = "...")]` into an error object, with the
+/// fields of the variant serialized into the `data` member. A field which can't be serialized
+/// into JSON, e.g. a map whose keys aren't strings, is replaced with a string describing the
+/// failure, so that the error object is still sent.
+pub trait IntoRpcError {
+    /// Converts into a JSON-RPC error object.
+    fn into_rpc_error(self) -> Error;
+}
+
+impl IntoRpcError for Error {
+    fn into_rpc_error(self) -> Error {
+        self
+    }
+}
+
+/// Invalid JSON is a `ParseError`, valid JSON that doesn't match the expected type is an
+/// `InvalidParams` error, and I/O failures are an `InternalError`.
+/// The JSON error is kept as the cause.
+impl IntoRpcError for serde_json::Error {
+    fn into_rpc_error(self) -> Error {
+        let error = match self.classify() {
+            Category::Syntax | Category::Eof => Error::parse_error(),
+            Category::Data => Error::invalid_params(&self),
+            Category::Io => Error::internal_error(),
+        };
+        error.with_cause(self)
+    }
+}
+
+/// Conversion of a JSON-RPC error object into an application error.
+///
+/// With the `derive` feature enabled, `#[derive(FromRpcError)]` converts an error object into the
+/// variant of an enum with the matching `#[rpc_error(code = ..)]`, mirroring `IntoRpcError`, or
+/// into the variant annotated with `#[rpc_error(other)]` which preserves any other error.
+pub trait FromRpcError<D = Value>: Sized {
+    /// Converts from a JSON-RPC error object.
+    fn from_rpc_error(error: Error<D>) -> Self;
+}
+
+impl<D> FromRpcError<D> for Error<D> {
+    fn from_rpc_error(error: Error<D>) -> Self {
+        error
     }
 }
 
@@ -209,4 +554,176 @@ mod tests {
             r#"{"code":-32600,"message":"Unsupported JSON-RPC protocol version"}"#
         );
     }
+
+    #[test]
+    fn error_code_ranges() {
+        assert_eq!(
+            ErrorCode::server_error(-32000),
+            Ok(ErrorCode::ServerError(-32000))
+        );
+        assert_eq!(
+            ErrorCode::server_error(-32099),
+            Ok(ErrorCode::ServerError(-32099))
+        );
+        assert_eq!(
+            ErrorCode::server_error(-32100),
+            Err(InvalidErrorCode(-32100))
+        );
+        assert_eq!(ErrorCode::server_error(1), Err(InvalidErrorCode(1)));
+        assert_eq!(
+            ErrorCode::application_defined(1),
+            Ok(ErrorCode::ServerError(1))
+        );
+        assert_eq!(
+            ErrorCode::application_defined(-31999),
+            Ok(ErrorCode::ServerError(-31999))
+        );
+        assert_eq!(
+            ErrorCode::application_defined(-32768),
+            Err(InvalidErrorCode(-32768))
+        );
+        assert_eq!(
+            ErrorCode::application_defined(-32050),
+            Err(InvalidErrorCode(-32050))
+        );
+
+        let cases = vec![
+            (ErrorCode::ParseError, true, false),
+            (ErrorCode::InternalError, true, false),
+            (ErrorCode::ServerError(-32000), true, true),
+            (ErrorCode::ServerError(-32500), true, false),
+            (ErrorCode::ServerError(-32769), false, false),
+            (ErrorCode::ServerError(100), false, false),
+        ];
+        for (code, reserved, server_error) in cases {
+            assert_eq!(code.is_reserved(), reserved, "{}", code);
+            assert_eq!(code.is_server_error_range(), server_error, "{}", code);
+            assert_eq!(code.is_application_defined(), !reserved, "{}", code);
+        }
+    }
+
+    #[test]
+    fn error_code_registry() {
+        assert_eq!(
+            ErrorCode::MethodNotFound.to_string(),
+            "Method not found (-32601)"
+        );
+
+        let mut registry = ErrorRegistry::new();
+        assert_eq!(registry.name(ErrorCode::ServerError(-32098)), None);
+        assert_eq!(
+            registry.description(ErrorCode::ServerError(-32098)),
+            "Server error"
+        );
+
+        registry.register(-32098, "Node is syncing").unwrap();
+        registry.register(4001, "Insufficient funds").unwrap();
+        assert_eq!(
+            registry.name(ErrorCode::ServerError(-32098)),
+            Some("Node is syncing")
+        );
+        assert_eq!(
+            serde_json::to_string(&registry.error(ErrorCode::ServerError(4001))).unwrap(),
+            r#"{"code":4001,"message":"Insufficient funds"}"#
+        );
+        assert_eq!(
+            registry.description(ErrorCode::InternalError),
+            "Internal error"
+        );
+
+        // Codes are only described by name through the registry.
+        assert_eq!(
+            ErrorCode::ServerError(-32098).to_string(),
+            "Server error (-32098)"
+        );
+        assert_eq!(
+            Error::new(ErrorCode::ServerError(4001)).message,
+            "Server error"
+        );
+
+        assert_eq!(
+            registry.register(-32603, "Oops"),
+            Err(InvalidErrorCode(-32603))
+        );
+        assert_eq!(
+            registry.register(-32700, "Oops"),
+            Err(InvalidErrorCode(-32700))
+        );
+    }
+
+    #[test]
+    fn error_data_conversion() {
+        let error = Error::invalid_params_with_details("unexpected params", "details");
+        let error = error.map_data(|data| data.as_str().map(str::len));
+        assert_eq!(error.data, Some(Some(7)));
+
+        let error = Error::with_data(ErrorCode::InternalError, "Internal error", Some(vec![1, 2]));
+        assert_eq!(
+            error.clone().try_data_into::<Value>().unwrap().data,
+            Some(Value::from(vec![1, 2]))
+        );
+        assert!(error.try_data_into::<String>().is_err());
+    }
+
+    #[test]
+    fn json_error_into_rpc_error() {
+        let err = serde_json::from_str::<Value>("{").unwrap_err();
+        let error = err.into_rpc_error();
+        assert_eq!(error.code, ErrorCode::ParseError);
+        assert_eq!(error.message, "Parse error");
+        assert_eq!(error.data, None);
+        assert!(error::Error::source(&error).is_some());
+
+        let err = serde_json::from_str::<u64>(r#""foo""#).unwrap_err();
+        assert_eq!(
+            err.into_rpc_error(),
+            Error::invalid_params("invalid type: string \"foo\", expected u64 at line 1 column 5")
+        );
+
+        assert_eq!(
+            Error::method_not_found().into_rpc_error(),
+            Error::method_not_found()
+        );
+    }
+
+    #[test]
+    fn error_cause() {
+        use std::error::Error as _;
+
+        let err = serde_json::from_str::<u64>(r#""foo""#).unwrap_err();
+        let error = Error::internal_error().with_cause(err);
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "invalid type: string \"foo\", expected u64 at line 1 column 5"
+        );
+        // The cause is neither serialized nor compared.
+        assert_eq!(error, Error::internal_error());
+        assert_eq!(
+            serde_json::to_string(&error).unwrap(),
+            r#"{"code":-32603,"message":"Internal error"}"#
+        );
+        assert!(Error::internal_error().source().is_none());
+
+        assert_eq!(error.clone().apply_cause_policy(&RedactCause).data, None);
+        assert_eq!(
+            error.clone().apply_cause_policy(&ExposeCause).data,
+            Some(Value::from(
+                "invalid type: string \"foo\", expected u64 at line 1 column 5"
+            ))
+        );
+        let policy =
+            |error: &Error, cause: &(dyn error::Error + Send + Sync + 'static)| match error.code {
+                ErrorCode::InternalError => None,
+                _ => Some(Value::String(cause.to_string())),
+            };
+        assert_eq!(error.apply_cause_policy(&policy).data, None);
+
+        // The data isn't replaced by the cause.
+        let error = Error::invalid_params_with_details("unexpected params", "details")
+            .with_cause(Error::method_not_found());
+        assert_eq!(
+            error.apply_cause_policy(&ExposeCause).data,
+            Some(Value::from("details"))
+        );
+    }
 }
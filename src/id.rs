@@ -1,6 +1,10 @@
-use std::fmt;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Serialize};
 
 /// Represents JSON-RPC request id.
 ///
@@ -10,17 +14,151 @@ use serde::{Deserialize, Serialize};
 ///
 /// The Server **MUST** reply with the same value in the Response object if included.
 /// This member is used to correlate the context between the two objects.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+///
+/// Numbers are compared, hashed and ordered by their value, so `Id::Int(1)` equals `Id::Num(1)`,
+/// though the conversions from integers always make non-negative numbers `Num`.
+/// Numbers with fractional parts and integers out of the `i64` or `u64` range are rejected.
+#[derive(Clone, Debug, Default, Serialize)]
 #[serde(untagged)]
 pub enum Id {
+    /// Null id
+    #[default]
+    Null,
     /// Numeric id
     Num(u64),
+    /// Negative numeric id
+    Int(i64),
     /// String id
     Str(String),
 }
 
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Null, Self::Null) => true,
+            (Self::Str(a), Self::Str(b)) => a == b,
+            _ => match (self.number(), other.number()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Id {}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Null => state.write_u8(0),
+            Self::Num(_) | Self::Int(_) => {
+                state.write_u8(1);
+                self.number().hash(state);
+            }
+            Self::Str(id) => {
+                state.write_u8(2);
+                id.hash(state);
+            }
+        }
+    }
+}
+
+/// Null ids come first, then numbers by value, then strings.
+impl Ord for Id {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (Self::Null, _) => Ordering::Less,
+            (_, Self::Null) => Ordering::Greater,
+            (Self::Str(a), Self::Str(b)) => a.cmp(b),
+            (Self::Str(_), _) => Ordering::Greater,
+            (_, Self::Str(_)) => Ordering::Less,
+            _ => self.number().cmp(&other.number()),
+        }
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'de> Deserialize<'de> for Id {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Id;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string, an integer or null")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::Null)
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::Null)
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::Num(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::from(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::Str(v.to_owned()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Id::Str(v))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
 impl Id {
+    /// Returns the value of a numeric id, which covers both the `u64` and `i64` range.
+    fn number(&self) -> Option<i128> {
+        match self {
+            Self::Num(id) => Some(i128::from(*id)),
+            Self::Int(id) => Some(i128::from(*id)),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Id` is Null. Returns false otherwise.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
     /// If the `Id` is an Number, returns the associated number. Returns None
     /// otherwise.
     pub fn as_number(&self) -> Option<&u64> {
@@ -30,6 +168,16 @@ impl Id {
         }
     }
 
+    /// If the `Id` is a Number that fits in an `i64`, returns the associated number.
+    /// Returns None otherwise.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Num(id) if *id <= i64::MAX as u64 => Some(*id as i64),
+            Self::Int(id) => Some(*id),
+            _ => None,
+        }
+    }
+
     /// If the `Id` is a String, returns the associated str. Returns None
     /// otherwise.
     pub fn as_str(&self) -> Option<&str> {
@@ -43,7 +191,9 @@ impl Id {
 impl fmt::Display for Id {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Null => f.write_str("null"),
             Self::Num(id) => write!(f, "{}", id),
+            Self::Int(id) => write!(f, "{}", id),
             Self::Str(id) => f.write_str(id),
         }
     }
@@ -55,12 +205,46 @@ impl From<u64> for Id {
     }
 }
 
+impl From<u32> for Id {
+    fn from(id: u32) -> Self {
+        Self::Num(u64::from(id))
+    }
+}
+
+impl From<i64> for Id {
+    fn from(id: i64) -> Self {
+        if id < 0 {
+            Self::Int(id)
+        } else {
+            Self::Num(id as u64)
+        }
+    }
+}
+
+impl From<i32> for Id {
+    fn from(id: i32) -> Self {
+        Self::from(i64::from(id))
+    }
+}
+
 impl From<String> for Id {
     fn from(id: String) -> Self {
         Self::Str(id)
     }
 }
 
+impl From<&str> for Id {
+    fn from(id: &str) -> Self {
+        Self::Str(id.to_owned())
+    }
+}
+
+impl From<Option<Id>> for Id {
+    fn from(id: Option<Id>) -> Self {
+        id.unwrap_or(Self::Null)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,7 +252,10 @@ mod tests {
     #[test]
     fn id_serialization() {
         let cases = vec![
+            (Id::Null, r#"null"#),
             (Id::Num(0), r#"0"#),
+            (Id::Num(u64::MAX), r#"18446744073709551615"#),
+            (Id::Int(-1), r#"-1"#),
             (Id::Str("1".into()), r#""1""#),
             (Id::Str("test".into()), r#""test""#),
         ];
@@ -88,4 +275,80 @@ mod tests {
             r#"[0,"1","test"]"#
         );
     }
+
+    #[test]
+    fn id_conversion() {
+        assert_eq!(Id::from(1u32), Id::Num(1));
+        assert_eq!(Id::from(1i32), Id::Num(1));
+        assert_eq!(Id::from(-1i32), Id::Int(-1));
+        assert_eq!(Id::from(i64::MIN), Id::Int(i64::MIN));
+        assert_eq!(Id::from("test"), Id::Str("test".into()));
+        assert_eq!(
+            Id::from("936da01f-9abd-4d9d-80c7-02af85c822a8"),
+            Id::Str("936da01f-9abd-4d9d-80c7-02af85c822a8".into())
+        );
+        assert_eq!(Id::from(None), Id::Null);
+        assert_eq!(Id::from(Some(Id::Num(1))), Id::Num(1));
+
+        assert_eq!(Id::Num(1).as_i64(), Some(1));
+        assert_eq!(Id::Int(-1).as_i64(), Some(-1));
+        assert_eq!(Id::Num(u64::MAX).as_i64(), None);
+        assert!(Id::Null.is_null());
+    }
+
+    #[test]
+    fn numeric_id_equality() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(id: &Id) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            id.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(Id::Int(1), Id::Num(1));
+        assert_eq!(hash(&Id::Int(1)), hash(&Id::Num(1)));
+        assert_ne!(Id::Num(1), Id::Str("1".into()));
+        assert_ne!(Id::Int(-1), Id::Num(u64::MAX));
+
+        let mut ids = vec![
+            Id::Str("a".into()),
+            Id::Num(2),
+            Id::Int(-2),
+            Id::Null,
+            Id::Int(1),
+            Id::Num(u64::MAX),
+            Id::Int(i64::MIN),
+        ];
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                Id::Null,
+                Id::Int(i64::MIN),
+                Id::Int(-2),
+                Id::Num(1),
+                Id::Num(2),
+                Id::Num(u64::MAX),
+                Id::Str("a".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_id() {
+        let cases = vec![
+            r#"1.2"#,
+            r#"-1.0"#,
+            r#"18446744073709551616"#,
+            r#"-9223372036854775809"#,
+            r#"true"#,
+            r#"[]"#,
+            r#"{}"#,
+        ];
+
+        for case in cases {
+            assert!(serde_json::from_str::<Id>(case).is_err());
+        }
+    }
 }
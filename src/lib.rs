@@ -39,7 +39,7 @@
 //! ## Creates JSON-RPC 1.0 response
 //!
 //! ```rust
-//! use jsonrpc_types::v1::{Value, Error, Output, Response};
+//! use jsonrpc_types::v1::{Value, Error, Id, Output, Response};
 //!
 //! // Creates a JSON-RPC 1.0 success response
 //! let success_response = Output::success(Value::Bool(true), 1.into());
@@ -50,7 +50,7 @@
 //! );
 //!
 //! // Creates a JSON-RPC 1.0 failure response
-//! let failure_response = Output::<Value>::failure(Error::invalid_request(), None);
+//! let failure_response = Output::<Value>::failure(Error::invalid_request(), Id::Null);
 //! let response2 = Response::Single(failure_response.clone());
 //! assert_eq!(
 //!     serde_json::to_string(&response2).unwrap(),
@@ -102,7 +102,7 @@
 //! ## Creates JSON-RPC 2.0 response
 //!
 //! ```rust
-//! use jsonrpc_types::{Value, Error, Success, Failure, Output, Response};
+//! use jsonrpc_types::{Value, Error, Id, Success, Failure, Output, Response};
 //!
 //! // Creates a JSON-RPC 2.0 success response
 //! let success = Success::new(Value::Bool(true), 1.into());
@@ -113,7 +113,7 @@
 //! );
 //!
 //! // Creates a JSON-RPC 2.0 failure response
-//! let failure = Failure::new(Error::invalid_request(), None);
+//! let failure = Failure::new(Error::invalid_request(), Id::Null);
 //! let response2 = Response::<Value>::Single(Output::Failure(failure.clone()));
 //! assert_eq!(
 //!     serde_json::to_string(&response2).unwrap(),
@@ -137,10 +137,52 @@
 // Export JSON-RPC 2.0 types by default
 pub use self::v2::*;
 
+pub use self::{
+    any::{AnyRequest, AnyResponse, ProtocolVersion},
+    convert::ConversionError,
+    redact::{Redact, Redaction},
+};
+
+/// JSON-RPC 2.0 client helpers.
+#[cfg(feature = "client")]
+pub mod client;
+/// Framing of JSON-RPC messages over byte streams.
+#[cfg(feature = "codec")]
+pub mod codec;
+/// JSON-RPC 2.0 method routers.
+#[cfg(feature = "server")]
+pub mod server;
 /// JSON-RPC 1.0 types.
 pub mod v1;
+/// JSON-RPC 1.1 working draft types.
+pub mod v1_1;
 /// JSON-RPC 2.0 types.
 pub mod v2;
 
+mod any;
+mod convert;
 mod error;
 mod id;
+mod raw;
+mod redact;
+
+// Not public API, used by the code generated by `jsonrpc-types-derive`.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::v2::params::{from_value, to_value};
+    pub use serde::{de::DeserializeOwned, Serialize};
+    pub use serde_json::Error as JsonError;
+
+    /// Serializes the data of an error, which is replaced with a string describing the failure
+    /// if it can't be serialized into JSON.
+    pub fn to_data<T: Serialize>(value: T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap_or_else(|err| {
+            serde_json::Value::String(format!("unserializable error data: {}", err))
+        })
+    }
+
+    /// Deserializes a field from the data of an error, a missing value is deserialized from Null.
+    pub fn from_data<T: DeserializeOwned>(value: Option<serde_json::Value>) -> Option<T> {
+        serde_json::from_value(value.unwrap_or_default()).ok()
+    }
+}
@@ -0,0 +1,15 @@
+use serde::de::{self, DeserializeOwned};
+use serde_json::value::RawValue;
+
+/// Decodes a message from its raw JSON, once its kind has been detected from its members.
+///
+/// The error of the decoder is raised as is, with its position within the message:
+/// `serde_json` takes the line and column back from the message of a custom error,
+/// rather than appending the position of the outer decoder to it.
+pub(crate) fn decode<T, E>(raw: &RawValue) -> Result<T, E>
+where
+    T: DeserializeOwned,
+    E: de::Error,
+{
+    serde_json::from_str(raw.get()).map_err(E::custom)
+}
@@ -0,0 +1,304 @@
+use std::{fmt, sync::Arc};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    any::AnyResponse,
+    error::{Error, ErrorCode},
+    v1, v1_1, v2,
+};
+
+type CorrelationFn = dyn Fn(&Error) -> String + Send + Sync;
+
+/// The policy rewriting the errors of responses before they are sent to the peer,
+/// so that internal details (e.g. stack traces) don't leak.
+///
+/// The default policy leaves the errors unchanged.
+///
+/// ```rust
+/// use jsonrpc_types::{Error, Output, Redact, Redaction};
+///
+/// let redaction = Redaction::new()
+///     .generic_messages(true)
+///     .correlation(|error| {
+///         // Logs the original error along with the token.
+///         "42".to_owned()
+///     });
+///
+/// let mut error = Error::internal_error();
+/// error.message = "thread 'main' panicked at src/main.rs:2:5".into();
+/// let output = Output::<u64>::failure(error, 1.into()).redact(&redaction);
+/// assert_eq!(
+///     output.to_string(),
+///     r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error","data":{"correlationId":"42"}},"id":1}"#
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct Redaction {
+    strip_data: bool,
+    generic_messages: bool,
+    correlation: Option<Arc<CorrelationFn>>,
+}
+
+impl fmt::Debug for Redaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Redaction")
+            .field("strip_data", &self.strip_data)
+            .field("generic_messages", &self.generic_messages)
+            .field("correlation", &self.correlation.is_some())
+            .finish()
+    }
+}
+
+impl Redaction {
+    /// Creates a policy which leaves the errors unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the `data` member of all errors.
+    pub fn strip_data(mut self, strip_data: bool) -> Self {
+        self.strip_data = strip_data;
+        self
+    }
+
+    /// Replaces the message of `InternalError` and `ServerError` errors with the description of
+    /// their code, e.g. `Internal error`.
+    pub fn generic_messages(mut self, generic_messages: bool) -> Self {
+        self.generic_messages = generic_messages;
+        self
+    }
+
+    /// Replaces the `data` member of all errors with `{"correlationId": token}`, where the token
+    /// is generated from the original error.
+    ///
+    /// The generator is the place to log the original error, including its cause,
+    /// so that it can be found by the token reported by the peer.
+    pub fn correlation<F>(mut self, correlation: F) -> Self
+    where
+        F: Fn(&Error) -> String + Send + Sync + 'static,
+    {
+        self.correlation = Some(Arc::new(correlation));
+        self
+    }
+}
+
+/// Responses whose errors can be rewritten by a [`Redaction`] policy.
+pub trait Redact {
+    /// Applies the redaction policy to the errors.
+    fn redact(self, redaction: &Redaction) -> Self;
+}
+
+impl Redact for Error {
+    fn redact(mut self, redaction: &Redaction) -> Self {
+        let token = redaction
+            .correlation
+            .as_ref()
+            .map(|correlation| correlation(&self));
+        if redaction.generic_messages {
+            if let ErrorCode::InternalError | ErrorCode::ServerError(_) = self.code {
+                self.message = self.code.description();
+            }
+        }
+        if let Some(token) = token {
+            let mut data = Map::new();
+            data.insert("correlationId".into(), Value::String(token));
+            self.data = Some(Value::Object(data));
+        } else if redaction.strip_data {
+            self.data = None;
+        }
+        self
+    }
+}
+
+impl<T> Redact for v1::Output<T> {
+    fn redact(mut self, redaction: &Redaction) -> Self {
+        self.error = self.error.map(|error| error.redact(redaction));
+        self
+    }
+}
+
+impl<T> Redact for v1::Response<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::Single(output) => Self::Single(output.redact(redaction)),
+            Self::Batch(outputs) => Self::Batch(outputs.redact(redaction)),
+        }
+    }
+}
+
+impl Redact for v1_1::Error {
+    fn redact(self, redaction: &Redaction) -> Self {
+        Error::from(self).redact(redaction).into()
+    }
+}
+
+impl Redact for v1_1::Failure {
+    fn redact(mut self, redaction: &Redaction) -> Self {
+        self.error = self.error.redact(redaction);
+        self
+    }
+}
+
+impl<T> Redact for v1_1::Output<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::Success(success) => Self::Success(success),
+            Self::Failure(failure) => Self::Failure(failure.redact(redaction)),
+        }
+    }
+}
+
+impl<T> Redact for v1_1::Response<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::Single(output) => Self::Single(output.redact(redaction)),
+            Self::Batch(outputs) => Self::Batch(outputs.redact(redaction)),
+        }
+    }
+}
+
+impl Redact for v2::Failure {
+    fn redact(mut self, redaction: &Redaction) -> Self {
+        self.error = self.error.redact(redaction);
+        self
+    }
+}
+
+impl<T> Redact for v2::Output<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::Success(success) => Self::Success(success),
+            Self::Failure(failure) => Self::Failure(failure.redact(redaction)),
+        }
+    }
+}
+
+impl<T> Redact for v2::Response<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::Single(output) => Self::Single(output.redact(redaction)),
+            Self::Batch(outputs) => Self::Batch(outputs.redact(redaction)),
+        }
+    }
+}
+
+impl<T> Redact for AnyResponse<T> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        match self {
+            Self::V1(response) => Self::V1(response.redact(redaction)),
+            Self::V1_1(response) => Self::V1_1(response.redact(redaction)),
+            Self::V2(response) => Self::V2(response.redact(redaction)),
+        }
+    }
+}
+
+impl<R: Redact> Redact for Vec<R> {
+    fn redact(self, redaction: &Redaction) -> Self {
+        self.into_iter().map(|r| r.redact(redaction)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::id::Id;
+
+    fn leaky_error(code: ErrorCode) -> Error {
+        Error::with_data(
+            code,
+            "thread 'main' panicked at src/main.rs:2:5",
+            Some(Value::from("stack backtrace: ...")),
+        )
+    }
+
+    #[test]
+    fn error_redaction() {
+        let error = leaky_error(ErrorCode::InternalError);
+        assert_eq!(error.clone().redact(&Redaction::new()), error);
+
+        let redaction = Redaction::new().strip_data(true).generic_messages(true);
+        let cases = vec![
+            (ErrorCode::InternalError, "Internal error"),
+            (ErrorCode::ServerError(-32000), "Server error"),
+            (
+                ErrorCode::InvalidParams,
+                "thread 'main' panicked at src/main.rs:2:5",
+            ),
+        ];
+        for (code, message) in cases {
+            let error = leaky_error(code).redact(&redaction);
+            assert_eq!(error.message, message);
+            assert_eq!(error.data, None);
+        }
+
+        let redaction = Redaction::new().strip_data(true);
+        let error = Error::invalid_params_with_details("unexpected params", "stack backtrace");
+        assert_eq!(
+            serde_json::to_string(&error.redact(&redaction)).unwrap(),
+            r#"{"code":-32602,"message":"Invalid parameters: unexpected params"}"#
+        );
+    }
+
+    #[test]
+    fn correlation_token() {
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let redaction = Redaction::new().strip_data(true).correlation({
+            let logged = logged.clone();
+            move |error| {
+                let mut logged = logged.lock().unwrap();
+                logged.push(error.message.clone());
+                logged.len().to_string()
+            }
+        });
+
+        let response = v2::Response::Batch(vec![
+            v2::Output::failure(leaky_error(ErrorCode::InternalError), 1.into()),
+            v2::Output::success(Value::Bool(true), 2.into()),
+            v2::Output::failure(Error::method_not_found(), 3.into()),
+        ]);
+        assert_eq!(
+            response.redact(&redaction).to_string(),
+            r#"[{"jsonrpc":"2.0","error":{"code":-32603,"message":"thread 'main' panicked at src/main.rs:2:5","data":{"correlationId":"1"}},"id":1},{"jsonrpc":"2.0","result":true,"id":2},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found","data":{"correlationId":"2"}},"id":3}]"#
+        );
+        assert_eq!(
+            *logged.lock().unwrap(),
+            vec![
+                "thread 'main' panicked at src/main.rs:2:5".to_owned(),
+                "Method not found".to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn response_redaction() {
+        let redaction = Redaction::new().strip_data(true).generic_messages(true);
+        let error = leaky_error(ErrorCode::ServerError(-32001));
+
+        let response = v1::Response::Single(v1::Output::<Value>::failure(error.clone(), 1.into()));
+        assert_eq!(
+            response.redact(&redaction).to_string(),
+            r#"{"result":null,"error":{"code":-32001,"message":"Server error"},"id":1}"#
+        );
+
+        let response = AnyResponse::V1_1(v1_1::Response::<Value>::Single(v1_1::Output::failure(
+            error.clone().into(),
+            Id::Null,
+        )));
+        assert_eq!(
+            response.redact(&redaction).to_string(),
+            r#"{"version":"1.1","error":{"name":"JSONRPCError","code":-32001,"message":"Server error"},"id":null}"#
+        );
+
+        let response = AnyResponse::V2(v2::Response::<Value>::Single(v2::Output::failure(
+            error,
+            1.into(),
+        )));
+        assert_eq!(
+            response.redact(&redaction).to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32001,"message":"Server error"},"id":1}"#
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::{collections::HashMap, fmt, future::Future, sync::Arc};
+
+use futures::{
+    future::{self, BoxFuture, FutureExt},
+    stream::{self, StreamExt},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use super::{
+    parse_params, split_call, to_batch_response, to_output, to_result, Calls, SharedCausePolicy,
+};
+use crate::{
+    error::{CausePolicy, Error},
+    redact::Redaction,
+    v2::{Call, Output, Params, Request, Response},
+};
+
+type Handler =
+    Box<dyn Fn(Option<Params>) -> BoxFuture<'static, Result<Value, Error>> + Send + Sync>;
+
+/// Dispatches JSON-RPC 2.0 requests to the asynchronous handlers registered by method name.
+///
+/// The calls of a batch are executed concurrently, at most [`AsyncRouter::max_concurrency`]
+/// at a time, and the outputs of the batch response keep the order of the calls.
+/// The router does not depend on any particular executor.
+///
+/// ```rust
+/// use jsonrpc_types::{server::AsyncRouter, Error, Output, Request, Response, Value};
+///
+/// let router = AsyncRouter::new()
+///     .method("add", |(a, b): (u64, u64)| async move { Ok::<_, Error>(a + b) });
+///
+/// let request: Request = r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1}"#.parse().unwrap();
+/// assert_eq!(
+///     futures::executor::block_on(router.handle(request)),
+///     Some(Response::Single(Output::success(Value::from(3), 1.into())))
+/// );
+/// ```
+#[derive(Default)]
+pub struct AsyncRouter {
+    methods: HashMap<String, Handler>,
+    cause_policy: Option<SharedCausePolicy>,
+    redaction: Redaction,
+    max_concurrency: Option<usize>,
+}
+
+impl fmt::Debug for AsyncRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncRouter")
+            .field("methods", &self.methods.keys().collect::<Vec<_>>())
+            .field("cause_policy", &self.cause_policy.is_some())
+            .field("redaction", &self.redaction)
+            .field("max_concurrency", &self.max_concurrency)
+            .finish()
+    }
+}
+
+impl AsyncRouter {
+    /// Creates an empty router, which executes all the calls of a batch concurrently.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of calls of a batch that are executed concurrently.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrency` is zero.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        assert!(max_concurrency > 0, "max concurrency must be positive");
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Registers the asynchronous handler of the method, replacing the previous one if any.
+    ///
+    /// The params of the call are parsed into `P` by [`Params::parse`], omitted params are
+    /// parsed as an empty array.
+    pub fn method<M, P, T, F, Fut>(mut self, method: M, handler: F) -> Self
+    where
+        M: Into<String>,
+        P: DeserializeOwned,
+        T: Serialize + 'static,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, Error>> + Send + 'static,
+    {
+        let handler = move |params: Option<Params>| match parse_params(params) {
+            Ok(params) => handler(params).map(to_result).boxed(),
+            Err(err) => future::ready(Err(err)).boxed(),
+        };
+        self.methods.insert(method.into(), Box::new(handler));
+        self
+    }
+
+    /// Registers the synchronous handler of the method, replacing the previous one if any.
+    pub fn sync_method<M, P, T, F>(mut self, method: M, handler: F) -> Self
+    where
+        M: Into<String>,
+        P: DeserializeOwned,
+        T: Serialize,
+        F: Fn(P) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        let handler = move |params: Option<Params>| {
+            let result = parse_params(params).and_then(&handler);
+            future::ready(to_result(result)).boxed()
+        };
+        self.methods.insert(method.into(), Box::new(handler));
+        self
+    }
+
+    /// Sets the policy which exposes the causes of the errors returned by the handlers,
+    /// see [`Error::apply_cause_policy`].
+    ///
+    /// By default, the causes are never exposed.
+    pub fn cause_policy<P>(mut self, cause_policy: P) -> Self
+    where
+        P: CausePolicy + Send + Sync + 'static,
+    {
+        self.cause_policy = Some(Arc::new(cause_policy));
+        self
+    }
+
+    /// Sets the policy which redacts the errors of the responses, e.g. to hide internal details.
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Checks if a handler is registered for the method.
+    pub fn has_method(&self, method: &str) -> bool {
+        self.methods.contains_key(method)
+    }
+
+    /// Handles a single call.
+    ///
+    /// Returns `None` for a notification, as the Server MUST NOT reply to it.
+    pub async fn handle_call(&self, call: Call) -> Option<Output> {
+        let (method, params, id) = split_call(call);
+        let result = match self.methods.get(&method) {
+            Some(handler) => handler(params).await,
+            None => Err(Error::method_not_found()),
+        };
+        to_output(result, id, self.cause_policy.as_ref(), &self.redaction)
+    }
+
+    /// Handles a single or batch request.
+    ///
+    /// Returns `None` if there is nothing to reply, i.e. the request only contains notifications.
+    pub async fn handle(&self, request: Request) -> Option<Response> {
+        self.dispatch(Calls::from_request(request, &self.redaction))
+            .await
+    }
+
+    /// Decodes and handles a single or batch request.
+    ///
+    /// Malformed requests and invalid calls of a batch are answered with failures,
+    /// as described in [`Request::from_slice_partial`].
+    pub async fn handle_slice(&self, request: &[u8]) -> Option<Response> {
+        self.dispatch(Calls::from_slice(request, &self.redaction))
+            .await
+    }
+
+    async fn dispatch(&self, calls: Calls) -> Option<Response> {
+        match calls {
+            Calls::Single(call) => self.handle_call(call).await.map(Response::Single),
+            Calls::Batch(calls) => {
+                let max_concurrency = self.max_concurrency.unwrap_or(calls.len());
+                let outputs = stream::iter(calls)
+                    .map(|call| async move {
+                        match call {
+                            Ok(call) => self.handle_call(call).await,
+                            Err(failure) => Some(failure),
+                        }
+                    })
+                    .buffered(max_concurrency)
+                    .collect()
+                    .await;
+                to_batch_response(outputs)
+            }
+            Calls::Invalid(response) => Some(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::{
+        id::Id,
+        v2::{MethodCall, Notification},
+    };
+
+    /// Yields to the executor once.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn handle_single_request() {
+        let router = AsyncRouter::new()
+            .method(
+                "add",
+                |(a, b): (u64, u64)| async move { Ok::<_, Error>(a + b) },
+            )
+            .sync_method("sub", |(a, b): (u64, u64)| Ok::<_, Error>(a - b));
+        assert!(router.has_method("add"));
+        assert!(router.has_method("sub"));
+
+        let call = MethodCall::new("sub", (3, 2), 1.into());
+        assert_eq!(
+            block_on(router.handle(Request::Single(call.into()))),
+            Some(Response::Single(Output::success(Value::from(1), 1.into())))
+        );
+
+        let call = MethodCall::new("add", (1,), 2.into());
+        assert_eq!(
+            block_on(router.handle(Request::Single(call.into()))),
+            Some(Response::Single(Output::failure(
+                Error::invalid_params("invalid length 1, expected a tuple of size 2"),
+                2.into()
+            )))
+        );
+
+        let call = MethodCall::new("unknown", (), 3.into());
+        assert_eq!(
+            block_on(router.handle(Request::Single(call.into()))),
+            Some(Response::Single(Output::failure(
+                Error::method_not_found(),
+                3.into()
+            )))
+        );
+
+        let notification = Notification::new("add", (1, 2));
+        assert_eq!(
+            block_on(router.handle(Request::Single(notification.into()))),
+            None
+        );
+
+        assert_eq!(
+            block_on(router.handle(Request::Batch(vec![]))),
+            Some(Response::Single(Output::invalid_request(Id::Null)))
+        );
+    }
+
+    #[test]
+    fn handle_batch_request_concurrently() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let router = {
+            let (running, max_running) = (running.clone(), max_running.clone());
+            AsyncRouter::new()
+                .max_concurrency(2)
+                .method("sleep", move |(n,): (u64,)| {
+                    let (running, max_running) = (running.clone(), max_running.clone());
+                    async move {
+                        let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(current, Ordering::SeqCst);
+                        // Calls with less yields finish first.
+                        for _ in 0..n {
+                            YieldNow(false).await;
+                        }
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok::<_, Error>(n)
+                    }
+                })
+        };
+
+        let request = Request::Batch(vec![
+            MethodCall::new("sleep", (3,), 1.into()).into(),
+            MethodCall::new("sleep", (1,), 2.into()).into(),
+            Notification::new("sleep", (0,)).into(),
+            MethodCall::new("sleep", (2,), 3.into()).into(),
+            MethodCall::new("unknown", (), 4.into()).into(),
+        ]);
+        assert_eq!(
+            block_on(router.handle(request)),
+            Some(Response::Batch(vec![
+                Output::success(Value::from(3), 1.into()),
+                Output::success(Value::from(1), 2.into()),
+                Output::success(Value::from(2), 3.into()),
+                Output::failure(Error::method_not_found(), 4.into()),
+            ]))
+        );
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(running.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn handle_slice_request() {
+        let router =
+            AsyncRouter::new().method(
+                "add",
+                |(a, b): (u64, u64)| async move { Ok::<_, Error>(a + b) },
+            );
+
+        let response = block_on(router.handle_slice(
+            br#"[{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1},{"foo":"boo"}]"#,
+        ))
+        .unwrap();
+        match response {
+            Response::Batch(outputs) => {
+                assert_eq!(outputs.len(), 2);
+                assert_eq!(outputs[0], Output::success(Value::from(3), 1.into()));
+                assert_eq!(outputs[1].id(), Id::Null);
+            }
+            Response::Single(_) => panic!("expected a batch response"),
+        }
+
+        let notifications = br#"[{"jsonrpc":"2.0","method":"add","params":[1,2]}]"#;
+        assert_eq!(block_on(router.handle_slice(notifications)), None);
+    }
+}
@@ -0,0 +1,116 @@
+/// Asynchronous JSON-RPC 2.0 method router
+#[cfg(feature = "async")]
+mod async_router;
+/// Synchronous JSON-RPC 2.0 method router
+mod router;
+
+#[cfg(feature = "async")]
+pub use self::async_router::AsyncRouter;
+pub use self::router::Router;
+
+use std::sync::Arc;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{CausePolicy, Error},
+    id::Id,
+    redact::{Redact, Redaction},
+    v2::{Call, Failure, Output, Params, PartialRequest, Request, Response},
+};
+
+type SharedCausePolicy = Arc<dyn CausePolicy + Send + Sync>;
+
+/// The calls of a request to be dispatched to the handlers.
+enum Calls {
+    /// A single call.
+    Single(Call),
+    /// The calls of a non-empty batch, the invalid ones are already answered with failures.
+    Batch(Vec<Result<Call, Output>>),
+    /// The response to a request which can't be dispatched, e.g. a malformed request or an
+    /// empty batch.
+    Invalid(Response),
+}
+
+impl Calls {
+    fn from_request(request: Request, redaction: &Redaction) -> Self {
+        match request {
+            Request::Single(call) => Self::Single(call),
+            Request::Batch(calls) => Self::batch(calls.into_iter().map(Ok).collect(), redaction),
+        }
+    }
+
+    /// Decodes the request, as described in [`Request::from_slice_partial`].
+    fn from_slice(request: &[u8], redaction: &Redaction) -> Self {
+        match Request::from_slice_partial(request) {
+            Ok(PartialRequest::Single(call)) => Self::Single(call),
+            Ok(PartialRequest::Batch(calls)) => Self::batch(calls, redaction),
+            Err(err) => {
+                let failure = Output::Failure(err.into_failure()).redact(redaction);
+                Self::Invalid(Response::Single(failure))
+            }
+        }
+    }
+
+    fn batch(calls: Vec<Result<Call, Failure>>, redaction: &Redaction) -> Self {
+        if calls.is_empty() {
+            let failure = Output::invalid_request(Id::Null).redact(redaction);
+            return Self::Invalid(Response::Single(failure));
+        }
+        let calls = calls
+            .into_iter()
+            .map(|call| call.map_err(|failure| Output::Failure(failure).redact(redaction)))
+            .collect();
+        Self::Batch(calls)
+    }
+}
+
+/// Splits the call into its method, params and id, the id of a notification is `None`.
+fn split_call(call: Call) -> (String, Option<Params>, Option<Id>) {
+    match call {
+        Call::MethodCall(call) => (call.method, call.params, Some(call.id)),
+        Call::Notification(notification) => (notification.method, notification.params, None),
+    }
+}
+
+/// Parses the params of a call, omitted params are parsed as an empty array.
+fn parse_params<P: DeserializeOwned>(params: Option<Params>) -> Result<P, Error> {
+    params.unwrap_or_default().parse::<P>()
+}
+
+/// Serializes the result of a handler.
+fn to_result<T: Serialize>(result: Result<T, Error>) -> Result<Value, Error> {
+    serde_json::to_value(result?).map_err(|err| Error::internal_error().with_cause(err))
+}
+
+/// Builds the output of a call, the Server MUST NOT reply to a notification.
+///
+/// The cause policy is applied to the error before the redaction.
+fn to_output(
+    result: Result<Value, Error>,
+    id: Option<Id>,
+    cause_policy: Option<&SharedCausePolicy>,
+    redaction: &Redaction,
+) -> Option<Output> {
+    let id = id?;
+    let output = match result {
+        Ok(result) => Output::success(result, id),
+        Err(error) => match cause_policy {
+            Some(policy) => Output::failure(error.apply_cause_policy(policy.as_ref()), id),
+            None => Output::failure(error, id),
+        },
+    };
+    Some(output.redact(redaction))
+}
+
+/// Builds the response of a batch, there is nothing to reply if the batch only contains
+/// notifications.
+fn to_batch_response(outputs: Vec<Option<Output>>) -> Option<Response> {
+    let outputs = outputs.into_iter().flatten().collect::<Vec<_>>();
+    if outputs.is_empty() {
+        None
+    } else {
+        Some(Response::Batch(outputs))
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use super::{
+    parse_params, split_call, to_batch_response, to_output, to_result, Calls, SharedCausePolicy,
+};
+use crate::{
+    error::{CausePolicy, Error},
+    redact::Redaction,
+    v2::{Call, Output, Params, Request, Response},
+};
+
+type Handler = Box<dyn Fn(Option<Params>) -> Result<Value, Error> + Send + Sync>;
+
+/// Dispatches JSON-RPC 2.0 requests to the handlers registered by method name.
+///
+/// ```rust
+/// use jsonrpc_types::{server::Router, Error, Output, Request, Response, Value};
+///
+/// let router = Router::new()
+///     .method("add", |(a, b): (u64, u64)| Ok::<_, Error>(a + b));
+///
+/// let request: Request = r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1}"#.parse().unwrap();
+/// assert_eq!(
+///     router.handle(request),
+///     Some(Response::Single(Output::success(Value::from(3), 1.into())))
+/// );
+/// ```
+#[derive(Default)]
+pub struct Router {
+    methods: HashMap<String, Handler>,
+    cause_policy: Option<SharedCausePolicy>,
+    redaction: Redaction,
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("methods", &self.methods.keys().collect::<Vec<_>>())
+            .field("cause_policy", &self.cause_policy.is_some())
+            .field("redaction", &self.redaction)
+            .finish()
+    }
+}
+
+impl Router {
+    /// Creates an empty router.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler of the method, replacing the previous one if any.
+    ///
+    /// The params of the call are parsed into `P` by [`Params::parse`], omitted params are
+    /// parsed as an empty array.
+    pub fn method<M, P, T, F>(mut self, method: M, handler: F) -> Self
+    where
+        M: Into<String>,
+        P: DeserializeOwned,
+        T: Serialize,
+        F: Fn(P) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        let handler = move |params: Option<Params>| to_result(handler(parse_params(params)?));
+        self.methods.insert(method.into(), Box::new(handler));
+        self
+    }
+
+    /// Sets the policy which exposes the causes of the errors returned by the handlers,
+    /// see [`Error::apply_cause_policy`].
+    ///
+    /// By default, the causes are never exposed.
+    pub fn cause_policy<P>(mut self, cause_policy: P) -> Self
+    where
+        P: CausePolicy + Send + Sync + 'static,
+    {
+        self.cause_policy = Some(Arc::new(cause_policy));
+        self
+    }
+
+    /// Sets the policy which redacts the errors of the responses, e.g. to hide internal details.
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Checks if a handler is registered for the method.
+    pub fn has_method(&self, method: &str) -> bool {
+        self.methods.contains_key(method)
+    }
+
+    /// Handles a single call.
+    ///
+    /// Returns `None` for a notification, as the Server MUST NOT reply to it.
+    pub fn handle_call(&self, call: Call) -> Option<Output> {
+        let (method, params, id) = split_call(call);
+        let result = match self.methods.get(&method) {
+            Some(handler) => handler(params),
+            None => Err(Error::method_not_found()),
+        };
+        to_output(result, id, self.cause_policy.as_ref(), &self.redaction)
+    }
+
+    /// Handles a single or batch request.
+    ///
+    /// Returns `None` if there is nothing to reply, i.e. the request only contains notifications.
+    pub fn handle(&self, request: Request) -> Option<Response> {
+        self.dispatch(Calls::from_request(request, &self.redaction))
+    }
+
+    /// Decodes and handles a single or batch request.
+    ///
+    /// Malformed requests and invalid calls of a batch are answered with failures,
+    /// as described in [`Request::from_slice_partial`].
+    pub fn handle_slice(&self, request: &[u8]) -> Option<Response> {
+        self.dispatch(Calls::from_slice(request, &self.redaction))
+    }
+
+    fn dispatch(&self, calls: Calls) -> Option<Response> {
+        match calls {
+            Calls::Single(call) => self.handle_call(call).map(Response::Single),
+            Calls::Batch(calls) => {
+                let outputs = calls
+                    .into_iter()
+                    .map(|call| match call {
+                        Ok(call) => self.handle_call(call),
+                        Err(failure) => Some(failure),
+                    })
+                    .collect();
+                to_batch_response(outputs)
+            }
+            Calls::Invalid(response) => Some(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::ExposeCause,
+        id::Id,
+        v2::{MethodCall, Notification},
+    };
+
+    fn router() -> Router {
+        Router::new()
+            .method("add", |(a, b): (u64, u64)| Ok::<_, Error>(a + b))
+            .method(
+                "fail",
+                |_: Vec<Value>| Err::<(), _>(Error::internal_error()),
+            )
+    }
+
+    #[test]
+    fn handle_single_request() {
+        let router = router();
+        assert!(router.has_method("add"));
+
+        let call = MethodCall::new("add", (1, 2), 1.into());
+        assert_eq!(
+            router.handle(Request::Single(call.into())),
+            Some(Response::Single(Output::success(Value::from(3), 1.into())))
+        );
+
+        let call = MethodCall::new("add", (1,), 2.into());
+        assert_eq!(
+            router.handle(Request::Single(call.into())),
+            Some(Response::Single(Output::failure(
+                Error::invalid_params("invalid length 1, expected a tuple of size 2"),
+                2.into()
+            )))
+        );
+
+        let call = MethodCall::new("unknown", (), 3.into());
+        assert_eq!(
+            router.handle(Request::Single(call.into())),
+            Some(Response::Single(Output::failure(
+                Error::method_not_found(),
+                3.into()
+            )))
+        );
+
+        let notification = Notification::new("add", (1, 2));
+        assert_eq!(router.handle(Request::Single(notification.into())), None);
+    }
+
+    #[test]
+    fn handle_batch_request() {
+        let router = router();
+
+        let request = Request::Batch(vec![
+            MethodCall::new("add", (1, 2), 1.into()).into(),
+            Notification::new("add", (1, 2)).into(),
+            MethodCall::new("fail", (), 2.into()).into(),
+        ]);
+        assert_eq!(
+            router.handle(request),
+            Some(Response::Batch(vec![
+                Output::success(Value::from(3), 1.into()),
+                Output::failure(Error::internal_error(), 2.into()),
+            ]))
+        );
+
+        let request = Request::Batch(vec![
+            Notification::new("add", (1, 2)).into(),
+            Notification::new("unknown", ()).into(),
+        ]);
+        assert_eq!(router.handle(request), None);
+
+        assert_eq!(
+            router.handle(Request::Batch(vec![])),
+            Some(Response::Single(Output::invalid_request(Id::Null)))
+        );
+    }
+
+    #[test]
+    fn handle_slice_request() {
+        let router = router();
+
+        let response = router
+            .handle_slice(
+                br#"[{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1},{"foo":"boo"}]"#,
+            )
+            .unwrap();
+        match response {
+            Response::Batch(outputs) => {
+                assert_eq!(outputs.len(), 2);
+                assert_eq!(outputs[0], Output::success(Value::from(3), 1.into()));
+                assert_eq!(outputs[1].id(), Id::Null);
+            }
+            Response::Single(_) => panic!("expected a batch response"),
+        }
+
+        let response = router
+            .handle_slice(br#"{"jsonrpc":"2.0","method""#)
+            .unwrap();
+        assert_eq!(
+            response.to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error","data":"Invalid JSON: EOF while parsing an object at line 1 column 25"},"id":null}"#
+        );
+    }
+
+    #[test]
+    fn handle_redacted_request() {
+        let router = router()
+            .method("leak", |_: Vec<Value>| {
+                Err::<(), _>(Error::invalid_params_with_details(
+                    "oops",
+                    "stack backtrace",
+                ))
+            })
+            .redaction(Redaction::new().strip_data(true).generic_messages(true));
+
+        let response = router
+            .handle_slice(br#"[{"jsonrpc":"2.0","method":"leak","id":1},{"jsonrpc":"2.0","method":"fail","id":2}]"#)
+            .unwrap();
+        assert_eq!(
+            response.to_string(),
+            r#"[{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid parameters: oops"},"id":1},{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error"},"id":2}]"#
+        );
+
+        let response = router
+            .handle_slice(br#"{"jsonrpc":"2.0","method""#)
+            .unwrap();
+        assert_eq!(
+            response.to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#
+        );
+    }
+
+    #[test]
+    fn handle_request_with_cause_policy() {
+        let router = || {
+            router().method("parse", |(s,): (String,)| {
+                s.parse::<u64>()
+                    .map_err(|err| Error::internal_error().with_cause(err))
+            })
+        };
+        let request = br#"{"jsonrpc":"2.0","method":"parse","params":["x"],"id":1}"#;
+
+        // The cause isn't exposed by default.
+        assert_eq!(
+            router().handle_slice(request).unwrap().to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error"},"id":1}"#
+        );
+
+        let response = router()
+            .cause_policy(ExposeCause)
+            .handle_slice(request)
+            .unwrap();
+        assert_eq!(
+            response.to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error","data":"invalid digit found in string"},"id":1}"#
+        );
+
+        // The exposed cause is still subject to the redaction.
+        let response = router()
+            .cause_policy(ExposeCause)
+            .redaction(Redaction::new().strip_data(true))
+            .handle_slice(request)
+            .unwrap();
+        assert_eq!(
+            response.to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error"},"id":1}"#
+        );
+    }
+}
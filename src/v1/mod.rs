@@ -8,7 +8,10 @@ pub use self::{
     response::{Output, Response},
 };
 pub use crate::{
-    error::{Error, ErrorCode},
+    error::{
+        CausePolicy, Error, ErrorCode, ErrorRegistry, ExposeCause, FromRpcError, IntoRpcError,
+        InvalidErrorCode, RedactCause,
+    },
     id::Id,
 };
 
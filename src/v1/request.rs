@@ -21,7 +21,8 @@ pub struct MethodCall {
     /// during the invocation of the method. This member MAY be omitted.
     pub params: Params,
     /// An identifier established by the Client.
-    /// If it is not included it is assumed to be a notification.
+    /// If it is Null it is assumed to be a notification.
+    #[serde(deserialize_with = "deserialize_method_call_id")]
     pub id: Id,
 }
 
@@ -43,6 +44,19 @@ impl MethodCall {
     }
 }
 
+fn deserialize_method_call_id<'de, D>(deserializer: D) -> Result<Id, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let id = Id::deserialize(deserializer)?;
+    if id.is_null() {
+        return Err(de::Error::custom(
+            "JSON-RPC 1.0 method call id MUST NOT be Null",
+        ));
+    }
+    Ok(id)
+}
+
 /// Represents JSON-RPC 1.0 request which is a notification.
 ///
 /// A Request object that is a Notification signifies the Client's lack of interest in the
@@ -79,7 +93,7 @@ impl ser::Serialize for Notification {
         let mut state = ser::Serializer::serialize_struct(serializer, "Notification", 3)?;
         ser::SerializeStruct::serialize_field(&mut state, "method", &self.method)?;
         ser::SerializeStruct::serialize_field(&mut state, "params", &self.params)?;
-        ser::SerializeStruct::serialize_field(&mut state, "id", &Option::<Id>::None)?;
+        ser::SerializeStruct::serialize_field(&mut state, "id", &Id::Null)?;
         ser::SerializeStruct::end(state)
     }
 }
@@ -108,7 +122,7 @@ impl<'de> de::Deserialize<'de> for Notification {
             {
                 let mut method = Option::<String>::None;
                 let mut params = Option::<Params>::None;
-                let mut id = Option::<Option<Id>>::None;
+                let mut id = Option::<Id>::None;
 
                 while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
                     match key {
@@ -128,7 +142,7 @@ impl<'de> de::Deserialize<'de> for Notification {
                             if id.is_some() {
                                 return Err(de::Error::duplicate_field("id"));
                             }
-                            id = Some(de::MapAccess::next_value::<Option<Id>>(&mut map)?)
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
                         }
                     }
                 }
@@ -136,7 +150,7 @@ impl<'de> de::Deserialize<'de> for Notification {
                 let method = method.ok_or_else(|| de::Error::missing_field("method"))?;
                 let params = params.ok_or_else(|| de::Error::missing_field("params"))?;
                 let id = id.ok_or_else(|| de::Error::missing_field("id"))?;
-                if id.is_some() {
+                if !id.is_null() {
                     return Err(de::Error::custom(
                         "JSON-RPC 1.0 notification id MUST be Null",
                     ));
@@ -306,7 +320,7 @@ mod request_field {
                 "method" => Ok(Field::Method),
                 "params" => Ok(Field::Params),
                 "id" => Ok(Field::Id),
-                _ => Err(de::Error::unknown_field(v, &FIELDS)),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
             }
         }
     }
@@ -434,7 +448,7 @@ mod tests {
             r#"[{"method":"foo","params":[],"id":1},{"method":"bar","params":[],"id":2}]"#;
         assert_eq!(serde_json::to_string(&batch_request).unwrap(), batch_expect);
         assert_eq!(
-            serde_json::from_str::<Request>(&batch_expect).unwrap(),
+            serde_json::from_str::<Request>(batch_expect).unwrap(),
             batch_request
         );
     }
@@ -25,7 +25,7 @@ pub struct Output<T = Value> {
     ///
     /// If there was an error in detecting the id in the Request object (e.g. Parse error/Invalid Request),
     /// it **MUST** be Null.
-    pub id: Option<Id>,
+    pub id: Id,
 }
 
 impl<T: Serialize> fmt::Display for Output<T> {
@@ -59,7 +59,7 @@ impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Output<T> {
             {
                 let mut result = Option::<Option<T>>::None;
                 let mut error = Option::<Option<Error>>::None;
-                let mut id = Option::<Option<Id>>::None;
+                let mut id = Option::<Id>::None;
 
                 while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
                     match key {
@@ -79,7 +79,7 @@ impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Output<T> {
                             if id.is_some() {
                                 return Err(de::Error::duplicate_field("id"));
                             }
-                            id = Some(de::MapAccess::next_value::<Option<Id>>(&mut map)?)
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
                         }
                     }
                 }
@@ -88,7 +88,7 @@ impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Output<T> {
                 let error = error.ok_or_else(|| de::Error::missing_field("error"))?;
                 let id = id.ok_or_else(|| de::Error::missing_field("id"))?;
                 let (result, error, id) = match (result, error, id) {
-                    (Some(value), None, Some(id)) => (Some(value), None, Some(id)),
+                    (Some(value), None, id) if !id.is_null() => (Some(value), None, id),
                     (None, Some(error), id) => (None, Some(error), id),
                     _ => return Err(de::Error::custom("Invalid JSON-RPC 1.0 response")),
                 };
@@ -114,12 +114,12 @@ impl<T: Serialize + DeserializeOwned> Output<T> {
         Self {
             result: Some(result),
             error: None,
-            id: Some(id),
+            id,
         }
     }
 
     /// Creates a JSON-RPC 1.0 failure response output.
-    pub fn failure(error: Error, id: Option<Id>) -> Self {
+    pub fn failure(error: Error, id: Id) -> Self {
         Self {
             result: None,
             error: Some(error),
@@ -128,7 +128,7 @@ impl<T: Serialize + DeserializeOwned> Output<T> {
     }
 
     /// Creates a new failure response output indicating malformed request.
-    pub fn invalid_request(id: Option<Id>) -> Self {
+    pub fn invalid_request(id: Id) -> Self {
         Output::failure(Error::new(ErrorCode::InvalidRequest), id)
     }
 }
@@ -198,7 +198,7 @@ mod response_field {
                 "result" => Ok(Field::Result),
                 "error" => Ok(Field::Error),
                 "id" => Ok(Field::Id),
-                _ => Err(de::Error::unknown_field(v, &FIELDS)),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
             }
         }
     }
@@ -215,7 +215,7 @@ mod tests {
                 Output {
                     result: Some(Value::Bool(true)),
                     error: None,
-                    id: Some(Id::Num(1)),
+                    id: Id::Num(1),
                 },
                 r#"{"result":true,"error":null,"id":1}"#,
             ),
@@ -224,7 +224,7 @@ mod tests {
                 Output {
                     result: None,
                     error: Some(Error::parse_error()),
-                    id: Some(Id::Num(1)),
+                    id: Id::Num(1),
                 },
                 r#"{"result":null,"error":{"code":-32700,"message":"Parse error"},"id":1}"#,
             ),
@@ -233,7 +233,7 @@ mod tests {
                 Output {
                     result: None,
                     error: Some(Error::parse_error()),
-                    id: None,
+                    id: Id::Null,
                 },
                 r#"{"result":null,"error":{"code":-32700,"message":"Parse error"},"id":null}"#,
             ),
@@ -262,12 +262,12 @@ mod tests {
             Output {
                 result: Some(Value::Bool(true)),
                 error: None,
-                id: Some(Id::Num(1)),
+                id: Id::Num(1),
             },
             Output {
                 result: Some(Value::Bool(false)),
                 error: None,
-                id: Some(Id::Num(2)),
+                id: Id::Num(2),
             },
         ]);
         let batch_expect =
@@ -277,7 +277,7 @@ mod tests {
             batch_expect
         );
         assert_eq!(
-            serde_json::from_str::<Response>(&batch_expect).unwrap(),
+            serde_json::from_str::<Response>(batch_expect).unwrap(),
             batch_response
         );
     }
@@ -0,0 +1,79 @@
+use std::{error, fmt};
+
+use serde::{de, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ErrorCode;
+
+/// The name of JSON-RPC 1.1 error objects.
+const ERROR_NAME: &str = "JSONRPCError";
+
+/// JSON-RPC 1.1 Error Object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Error {
+    /// A String containing the name of the error.
+    /// This MUST be `JSONRPCError`, any other name is rejected on decode.
+    #[serde(deserialize_with = "deserialize_name")]
+    pub name: String,
+    /// A Number that indicates the error type that occurred.
+    pub code: ErrorCode,
+    /// A String providing a short description of the error.
+    pub message: String,
+    /// A JSON value that carries additional information about the error.
+    /// This may be omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+fn deserialize_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    if name != ERROR_NAME {
+        return Err(de::Error::invalid_value(
+            de::Unexpected::Str(&name),
+            &ERROR_NAME,
+        ));
+    }
+    Ok(name)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code.description(), self.message)
+    }
+}
+
+impl error::Error for Error {}
+
+impl Error {
+    /// Wraps given `ErrorCode`.
+    pub fn new(code: ErrorCode) -> Self {
+        Error {
+            name: ERROR_NAME.to_owned(),
+            message: code.description(),
+            code,
+            error: None,
+        }
+    }
+}
+
+impl From<crate::error::Error> for Error {
+    fn from(err: crate::error::Error) -> Self {
+        Error {
+            name: ERROR_NAME.to_owned(),
+            code: err.code,
+            message: err.message,
+            error: err.data,
+        }
+    }
+}
+
+/// The name of the error is dropped, since it is always `JSONRPCError`.
+impl From<Error> for crate::error::Error {
+    fn from(err: Error) -> Self {
+        crate::error::Error::with_data(err.code, err.message, err.error)
+    }
+}
@@ -0,0 +1,19 @@
+/// JSON-RPC 1.1 error objects
+mod error;
+/// JSON-RPC 1.1 request objects
+mod request;
+/// JSON-RPC 1.1 response objects
+mod response;
+/// JSON-RPC 1.1 protocol version
+mod version;
+
+pub use self::{
+    error::Error,
+    request::{Call, MethodCall, Notification, Request},
+    response::{Failure, Output, Response, Success},
+    version::Version,
+};
+pub use crate::{error::ErrorCode, id::Id, v2::Params};
+
+// Re-exports
+pub use serde_json::{Map, Value};
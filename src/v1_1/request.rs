@@ -0,0 +1,403 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{de, Deserialize, Serialize};
+
+use crate::{
+    id::Id,
+    v1_1::version::Version,
+    v2::{Params, ToParams},
+};
+
+/// Represents JSON-RPC 1.1 request which is a method call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MethodCall {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub version: Version,
+    /// A String containing the name of the method to be invoked.
+    pub method: String,
+    /// A Structured value that holds the parameter values to be used
+    /// during the invocation of the method. This member MAY be omitted.
+    ///
+    /// Parameters are either positional through an Array or named through an Object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Params>,
+    /// An identifier established by the Client.
+    /// If it is not included it is assumed to be a notification.
+    pub id: Id,
+}
+
+impl fmt::Display for MethodCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`MethodCall` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl MethodCall {
+    /// Creates a JSON-RPC 1.1 request which is a method call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters can't be serialized into JSON, see [`MethodCall::try_new`].
+    pub fn new<M: Into<String>, P: ToParams>(method: M, params: P, id: Id) -> Self {
+        Self::try_new(method, params, id).expect("parameter values are serializable")
+    }
+
+    /// Creates a JSON-RPC 1.1 request which is a method call,
+    /// failing if the parameters can't be serialized into JSON.
+    pub fn try_new<M: Into<String>, P: ToParams>(
+        method: M,
+        params: P,
+        id: Id,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            version: Version::V1_1,
+            method: method.into(),
+            params: params.try_to_params()?,
+            id,
+        })
+    }
+}
+
+/// Represents JSON-RPC 1.1 request which is a notification.
+///
+/// For JSON-RPC 1.1 working draft, a notification is a request without the id member.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Notification {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub version: Version,
+    /// A String containing the name of the method to be invoked.
+    pub method: String,
+    /// A Structured value that holds the parameter values to be used
+    /// during the invocation of the method. This member MAY be omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Params>,
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Notification` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl Notification {
+    /// Creates a JSON-RPC 1.1 request which is a notification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters can't be serialized into JSON, see [`Notification::try_new`].
+    pub fn new<M: Into<String>, P: ToParams>(method: M, params: P) -> Self {
+        Self::try_new(method, params).expect("parameter values are serializable")
+    }
+
+    /// Creates a JSON-RPC 1.1 request which is a notification,
+    /// failing if the parameters can't be serialized into JSON.
+    pub fn try_new<M: Into<String>, P: ToParams>(
+        method: M,
+        params: P,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            version: Version::V1_1,
+            method: method.into(),
+            params: params.try_to_params()?,
+        })
+    }
+}
+
+/// Represents single JSON-RPC 1.1 call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Call {
+    /// Call method
+    MethodCall(MethodCall),
+    /// Fire notification
+    Notification(Notification),
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Call` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Call {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::request_field::{Field, FIELDS};
+
+        struct Visitor<'de> {
+            marker: PhantomData<Call>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = Call;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct Call")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut version = Option::<Version>::None;
+                let mut method = Option::<String>::None;
+                let mut params = Option::<Option<Params>>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Version => {
+                            if version.is_some() {
+                                return Err(de::Error::duplicate_field("version"));
+                            }
+                            version = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Method => {
+                            if method.is_some() {
+                                return Err(de::Error::duplicate_field("method"));
+                            }
+                            method = Some(de::MapAccess::next_value::<String>(&mut map)?)
+                        }
+                        Field::Params => {
+                            if params.is_some() {
+                                return Err(de::Error::duplicate_field("params"));
+                            }
+                            params = Some(de::MapAccess::next_value::<Option<Params>>(&mut map)?)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let version = version.ok_or_else(|| de::Error::missing_field("version"))?;
+                let method = method.ok_or_else(|| de::Error::missing_field("method"))?;
+                let params = params.flatten();
+                Ok(match id {
+                    Some(id) => Call::MethodCall(MethodCall {
+                        version,
+                        method,
+                        params,
+                        id,
+                    }),
+                    None => Call::Notification(Notification {
+                        version,
+                        method,
+                        params,
+                    }),
+                })
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "Call",
+            FIELDS,
+            Visitor {
+                marker: PhantomData::<Call>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
+impl Call {
+    /// Returns the method of the request call.
+    pub fn method(&self) -> &str {
+        match self {
+            Self::MethodCall(call) => &call.method,
+            Self::Notification(notification) => &notification.method,
+        }
+    }
+
+    /// Returns the params of the request call.
+    pub fn params(&self) -> &Option<Params> {
+        match self {
+            Self::MethodCall(call) => &call.params,
+            Self::Notification(notification) => &notification.params,
+        }
+    }
+
+    /// Returns the id of the request call.
+    pub fn id(&self) -> Option<Id> {
+        match self {
+            Self::MethodCall(call) => Some(call.id.clone()),
+            Self::Notification(_notification) => None,
+        }
+    }
+}
+
+impl From<MethodCall> for Call {
+    fn from(call: MethodCall) -> Self {
+        Self::MethodCall(call)
+    }
+}
+
+impl From<Notification> for Call {
+    fn from(notify: Notification) -> Self {
+        Self::Notification(notify)
+    }
+}
+
+/// JSON-RPC 1.1 Request object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Request {
+    /// Single call
+    Single(Call),
+    /// Batch of calls
+    Batch(Vec<Call>),
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Request` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Request {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de> {
+            marker: PhantomData<Request>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = Request;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single call or a batch of calls")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let call = Call::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Request::Single(call))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut calls = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(call) = de::SeqAccess::next_element::<Call>(&mut seq)? {
+                    calls.push(call);
+                }
+                Ok(Request::Batch(calls))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<Request>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
+mod request_field {
+    use super::*;
+
+    pub const FIELDS: &[&str] = &["version", "method", "params", "id"];
+    pub enum Field {
+        Version,
+        Method,
+        Params,
+        Id,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "version" => Ok(Field::Version),
+                "method" => Ok(Field::Method),
+                "params" => Ok(Field::Params),
+                "id" => Ok(Field::Id),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map, Value};
+
+    use super::*;
+
+    #[test]
+    fn request_serialization() {
+        let mut named = Map::new();
+        named.insert("name".into(), Value::from("foo"));
+        let cases = vec![
+            (
+                Request::Single(MethodCall::new("foo", (1,), 1.into()).into()),
+                r#"{"version":"1.1","method":"foo","params":[1],"id":1}"#,
+            ),
+            (
+                Request::Single(MethodCall::new("foo", Some(Params::Map(named)), 1.into()).into()),
+                r#"{"version":"1.1","method":"foo","params":{"name":"foo"},"id":1}"#,
+            ),
+            (
+                Request::Batch(vec![
+                    Notification::new("foo", ()).into(),
+                    MethodCall::new("bar", (), "a".into()).into(),
+                ]),
+                r#"[{"version":"1.1","method":"foo"},{"version":"1.1","method":"bar","id":"a"}]"#,
+            ),
+        ];
+        for (request, expect) in cases {
+            assert_eq!(request.to_string(), expect);
+            let decoded: Request = serde_json::from_str(expect).unwrap();
+            assert_eq!(decoded, request);
+        }
+
+        let invalid_cases = vec![
+            json!({"method": "foo", "id": 1}),
+            json!({"version": "1.0", "method": "foo", "id": 1}),
+            json!({"jsonrpc": "2.0", "method": "foo", "id": 1}),
+            json!({"version": "1.1", "method": "foo", "params": 1}),
+            json!({"version": "1.1", "method": "foo", "id": 1, "extra": 1}),
+            json!([{"version": "1.1", "method": "foo"}, 1]),
+        ];
+        for case in invalid_cases {
+            assert!(serde_json::from_value::<Request>(case).is_err());
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Serialize,
+};
+use serde_json::Value;
+
+use crate::{
+    id::Id,
+    v1_1::{error::Error, version::Version},
+};
+
+/// Represents JSON-RPC 1.1 success response.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Success<T = Value> {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub version: Version,
+    /// Successful execution result.
+    pub result: T,
+    /// Correlation id.
+    ///
+    /// It **MUST** be the same as the value of the id member in the Request Object.
+    pub id: Id,
+}
+
+impl<T: Serialize> fmt::Display for Success<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Success` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Success<T> {
+    /// Creates a JSON-RPC 1.1 success response.
+    pub fn new(result: T, id: Id) -> Self {
+        Self {
+            version: Version::V1_1,
+            result,
+            id,
+        }
+    }
+}
+
+/// Represents JSON-RPC 1.1 failure response.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Failure {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub version: Version,
+    /// Failed execution error.
+    pub error: Error,
+    /// Correlation id.
+    ///
+    /// It **MUST** be the same as the value of the id member in the Request Object.
+    ///
+    /// If there was an error in detecting the id in the Request object (e.g. Parse error/Invalid Request),
+    /// it **MUST** be Null.
+    pub id: Id,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Failure` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl Failure {
+    /// Creates a JSON-RPC 1.1 failure response.
+    pub fn new(error: Error, id: Id) -> Self {
+        Self {
+            version: Version::V1_1,
+            error,
+            id,
+        }
+    }
+}
+
+/// Represents success / failure output of JSON-RPC 1.1 response.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Output<T = Value> {
+    /// Success response output
+    Success(Success<T>),
+    /// Failure response output
+    Failure(Failure),
+}
+
+impl<T: Serialize> fmt::Display for Output<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Output` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Output<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::response_field::{Field, FIELDS};
+
+        struct Visitor<'de, T> {
+            marker: PhantomData<Output<T>>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de, T: Deserialize<'de>> de::Visitor<'de> for Visitor<'de, T> {
+            type Value = Output<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct Output")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut version = Option::<Version>::None;
+                let mut result = Option::<T>::None;
+                let mut error = Option::<Error>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Version => {
+                            if version.is_some() {
+                                return Err(de::Error::duplicate_field("version"));
+                            }
+                            version = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Result => {
+                            if result.is_some() {
+                                return Err(de::Error::duplicate_field("result"));
+                            }
+                            result = Some(de::MapAccess::next_value::<T>(&mut map)?)
+                        }
+                        Field::Error => {
+                            if error.is_some() {
+                                return Err(de::Error::duplicate_field("error"));
+                            }
+                            error = Some(de::MapAccess::next_value::<Error>(&mut map)?)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let version = version.ok_or_else(|| de::Error::missing_field("version"))?;
+                let id = id.ok_or_else(|| de::Error::missing_field("id"))?;
+                match (result, error) {
+                    (Some(result), None) => Ok(Output::Success(Success {
+                        version,
+                        result,
+                        id,
+                    })),
+                    (None, Some(error)) => Ok(Output::Failure(Failure { version, error, id })),
+                    _ => Err(de::Error::custom("Invalid JSON-RPC 1.1 response")),
+                }
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "Output",
+            FIELDS,
+            Visitor {
+                marker: PhantomData::<Output<T>>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Output<T> {
+    /// Creates a JSON-RPC 1.1 success response output.
+    pub fn success(result: T, id: Id) -> Self {
+        Self::Success(Success::new(result, id))
+    }
+
+    /// Creates a JSON-RPC 1.1 failure response output.
+    pub fn failure(error: Error, id: Id) -> Self {
+        Self::Failure(Failure::new(error, id))
+    }
+
+    /// Gets the correlation id.
+    pub fn id(&self) -> Id {
+        match self {
+            Self::Success(s) => s.id.clone(),
+            Self::Failure(f) => f.id.clone(),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> From<Output<T>> for Result<T, Error> {
+    // Convert into a result.
+    // Will be `Ok` if it is a `SuccessResponse` and `Err` if `FailureResponse`.
+    fn from(output: Output<T>) -> Result<T, Error> {
+        match output {
+            Output::Success(s) => Ok(s.result),
+            Output::Failure(f) => Err(f.error),
+        }
+    }
+}
+
+/// JSON-RPC 1.1 Response object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Response<T = Value> {
+    /// Single response
+    Single(Output<T>),
+    /// Response to batch request (batch of responses)
+    Batch(Vec<Output<T>>),
+}
+
+impl<T: Serialize> fmt::Display for Response<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Response` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Response<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de, T> {
+            marker: PhantomData<Response<T>>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de, T: Deserialize<'de>> de::Visitor<'de> for Visitor<'de, T> {
+            type Value = Response<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single output or a batch of outputs")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let output = Output::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Response::Single(output))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut outputs = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(output) = de::SeqAccess::next_element::<Output<T>>(&mut seq)? {
+                    outputs.push(output);
+                }
+                Ok(Response::Batch(outputs))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<Response<T>>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
+mod response_field {
+    use super::*;
+
+    pub const FIELDS: &[&str] = &["version", "result", "error", "id"];
+    pub enum Field {
+        Version,
+        Result,
+        Error,
+        Id,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "version" => Ok(Field::Version),
+                "result" => Ok(Field::Result),
+                "error" => Ok(Field::Error),
+                "id" => Ok(Field::Id),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn response_serialization() {
+        let mut error = Error::new(ErrorCode::ServerError(123));
+        error.error = Some(Value::from("details"));
+        let cases = vec![
+            (
+                Response::Single(Output::success(Value::Bool(true), 1.into())),
+                r#"{"version":"1.1","result":true,"id":1}"#,
+            ),
+            (
+                Response::Single(Output::failure(error, 1.into())),
+                r#"{"version":"1.1","error":{"name":"JSONRPCError","code":123,"message":"Server error","error":"details"},"id":1}"#,
+            ),
+            (
+                Response::Batch(vec![
+                    Output::success(Value::Bool(true), 1.into()),
+                    Output::failure(Error::new(ErrorCode::MethodNotFound), Id::Null),
+                ]),
+                r#"[{"version":"1.1","result":true,"id":1},{"version":"1.1","error":{"name":"JSONRPCError","code":-32601,"message":"Method not found"},"id":null}]"#,
+            ),
+        ];
+        for (response, expect) in cases {
+            assert_eq!(response.to_string(), expect);
+            let decoded: Response = serde_json::from_str(expect).unwrap();
+            assert_eq!(decoded, response);
+        }
+
+        let invalid_cases = vec![
+            json!({"result": true, "error": null, "id": 1}),
+            json!({"version": "1.1", "id": 1}),
+            json!({"version": "1.1", "error": {"code": 1, "message": "foo"}, "id": 1}),
+            json!({"version": "1.1", "error": {"name": "Error", "code": 1, "message": "foo"}, "id": 1}),
+            json!({"version": "1.1", "result": true, "error": {"name": "JSONRPCError", "code": 1, "message": "foo"}, "id": 1}),
+            json!({"version": "1.1", "result": true}),
+            json!({"version": "1.1", "result": true, "id": 1, "extra": 1}),
+            json!([{"version": "1.1", "result": true, "id": 1}, 1]),
+        ];
+        for case in invalid_cases {
+            assert!(serde_json::from_value::<Response>(case).is_err());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use serde::{de, ser};
+
+/// JSON-RPC 1.1 Protocol Version.
+///
+/// The JSON-RPC 1.2 proposal evolved into JSON-RPC 2.0, so its payloads are handled by
+/// [`v2::Version`](crate::v2::Version).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Version {
+    /// JSON-RPC 1.1
+    V1_1,
+}
+
+impl ser::Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Version::V1_1 => serializer.serialize_str("1.1"),
+        }
+    }
+}
+
+impl<'a> de::Deserialize<'a> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Version, D::Error>
+    where
+        D: de::Deserializer<'a>,
+    {
+        deserializer.deserialize_identifier(VersionVisitor)
+    }
+}
+
+struct VersionVisitor;
+impl<'a> de::Visitor<'a> for VersionVisitor {
+    type Value = Version;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match value {
+            "1.1" => Ok(Version::V1_1),
+            _ => Err(de::Error::custom("Invalid JSON-RPC protocol version")),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{borrow::Cow, fmt};
+
+use serde::de;
+use serde_json::value::RawValue;
+
+/// A string that is borrowed from the input whenever it contains no escape sequences.
+pub(crate) struct CowStr<'a>(pub(crate) Cow<'a, str>);
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+        impl<'a> de::Visitor<'a> for Visitor {
+            type Value = CowStr<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Owned(v)))
+            }
+        }
+
+        de::Deserializer::deserialize_str(deserializer, Visitor)
+    }
+}
+
+/// Borrows a raw JSON value from the input.
+pub(crate) fn deserialize_raw<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Cow<'a, RawValue>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let raw = <&'a RawValue as de::Deserialize>::deserialize(deserializer)?;
+    Ok(Cow::Borrowed(raw))
+}
+
+/// Borrows the raw JSON text of optional request parameters from the input.
+pub(crate) fn deserialize_raw_params<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, RawValue>>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let raw = <Option<&'a RawValue> as de::Deserialize>::deserialize(deserializer)?;
+    check_raw_params(raw)?;
+    Ok(raw.map(Cow::Borrowed))
+}
+
+/// Checks that the raw parameters, if present, are a Structured value (an Array or an Object).
+pub(crate) fn check_raw_params<E>(raw: Option<&RawValue>) -> Result<(), E>
+where
+    E: de::Error,
+{
+    match raw {
+        Some(raw) if !matches!(raw.get().as_bytes().first(), Some(b'[') | Some(b'{')) => Err(
+            de::Error::invalid_type(de::Unexpected::Other(raw.get()), &"an array or an object"),
+        ),
+        _ => Ok(()),
+    }
+}
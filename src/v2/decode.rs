@@ -0,0 +1,286 @@
+use std::{error, fmt};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{Error, ErrorCode},
+    id::Id,
+    v2::{request::request_field::FIELDS, response::Failure},
+};
+
+/// Represents the reason why an incoming JSON-RPC 2.0 request could not be decoded.
+///
+/// Each variant maps to the error object the Server should reply with, see [`DecodeError::error`].
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Invalid JSON was received.
+    Parse(serde_json::Error),
+    /// The `jsonrpc` member is missing or is not exactly "2.0".
+    InvalidVersion {
+        /// The id of the request, if it could be recovered.
+        id: Option<Id>,
+    },
+    /// The `method` member is missing.
+    MissingMethod {
+        /// The id of the request, if it could be recovered.
+        id: Option<Id>,
+    },
+    /// The `id` member is neither a String nor a Number.
+    InvalidId,
+    /// The request contains a member that is not defined by the specification.
+    UnknownField {
+        /// The name of the unknown member.
+        field: String,
+        /// The id of the request, if it could be recovered.
+        id: Option<Id>,
+    },
+    /// The request is an empty batch.
+    EmptyBatch,
+    /// The JSON is not a valid Request object for any other reason.
+    InvalidRequest {
+        /// The description of the problem.
+        reason: String,
+        /// The id of the request, if it could be recovered.
+        id: Option<Id>,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "Invalid JSON: {}", err),
+            Self::InvalidVersion { .. } => f.write_str("Unsupported JSON-RPC protocol version"),
+            Self::MissingMethod { .. } => f.write_str("Missing field `method`"),
+            Self::InvalidId => f.write_str("Invalid type of field `id`"),
+            Self::UnknownField { field, .. } => write!(f, "Unknown field `{}`", field),
+            Self::EmptyBatch => f.write_str("Empty batch"),
+            Self::InvalidRequest { reason, .. } => f.write_str(reason),
+        }
+    }
+}
+
+impl error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl DecodeError {
+    /// Returns the id of the request, if it could be recovered.
+    pub fn id(&self) -> Option<&Id> {
+        match self {
+            Self::Parse(_) | Self::InvalidId | Self::EmptyBatch => None,
+            Self::InvalidVersion { id }
+            | Self::MissingMethod { id }
+            | Self::UnknownField { id, .. }
+            | Self::InvalidRequest { id, .. } => id.as_ref(),
+        }
+    }
+
+    /// Returns the JSON-RPC error object that describes this decode error.
+    ///
+    /// Malformed JSON is reported as `ParseError`, everything else as `InvalidRequest`.
+    pub fn error(&self) -> Error {
+        let code = match self {
+            Self::Parse(_) => ErrorCode::ParseError,
+            Self::InvalidVersion { .. } => return Error::invalid_version(),
+            _ => ErrorCode::InvalidRequest,
+        };
+        Error::with_data(
+            code,
+            code.description(),
+            Some(Value::String(self.to_string())),
+        )
+    }
+
+    /// Converts the decode error into a failure response that is ready to be sent.
+    pub fn into_failure(self) -> Failure {
+        Failure::new(self.error(), self.id().cloned().unwrap_or_default())
+    }
+
+    /// Works out why `input` could not be decoded, given the error of the fast path.
+    ///
+    /// The error of a batch is reported as is, since the ids of its calls don't identify the
+    /// whole request. Only a single call is parsed again to recover its id, this is on the
+    /// error path so it does not affect the cost of decoding valid requests.
+    pub(crate) fn classify(input: &[u8], err: serde_json::Error) -> Self {
+        if !err.is_data() {
+            return Self::Parse(err);
+        }
+        if is_batch(input) {
+            return Self::InvalidRequest {
+                reason: err.to_string(),
+                id: None,
+            };
+        }
+        match serde_json::from_slice::<Value>(input) {
+            Ok(call) => Self::from_call(&call, err),
+            Err(err) => Self::Parse(err),
+        }
+    }
+
+    /// Classifies a single call that failed to decode with `err`.
+    pub(crate) fn from_call(call: &Value, err: serde_json::Error) -> Self {
+        let object = match call {
+            Value::Object(object) => object,
+            _ => {
+                return Self::InvalidRequest {
+                    reason: err.to_string(),
+                    id: None,
+                }
+            }
+        };
+
+        let id = match object.get("id") {
+            None => None,
+            Some(id) => match Id::deserialize(id) {
+                Ok(id) => Some(id),
+                Err(_) => return Self::InvalidId,
+            },
+        };
+        if object.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+            return Self::InvalidVersion { id };
+        }
+        if !object.contains_key("method") {
+            return Self::MissingMethod { id };
+        }
+        if let Some(field) = unknown_field(object) {
+            return Self::UnknownField {
+                field: field.to_owned(),
+                id,
+            };
+        }
+        Self::InvalidRequest {
+            reason: err.to_string(),
+            id,
+        }
+    }
+}
+
+impl From<DecodeError> for Failure {
+    fn from(err: DecodeError) -> Self {
+        err.into_failure()
+    }
+}
+
+/// Checks if the input is a JSON array, without parsing it.
+fn is_batch(input: &[u8]) -> bool {
+    input
+        .iter()
+        .find(|byte| !byte.is_ascii_whitespace())
+        .is_some_and(|byte| *byte == b'[')
+}
+
+fn unknown_field(object: &Map<String, Value>) -> Option<&str> {
+    object
+        .keys()
+        .map(String::as_str)
+        .find(|key| !FIELDS.contains(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::ErrorCode, v2::request::Request};
+
+    #[test]
+    fn decode_error_classification() {
+        let cases = vec![
+            (
+                r#"{"jsonrpc":"2.0","method":"foo","id":1"#,
+                ErrorCode::ParseError,
+                None,
+            ),
+            (
+                r#"{"jsonrpc":"1.0","method":"foo","id":1}"#,
+                ErrorCode::InvalidRequest,
+                Some(Id::Num(1)),
+            ),
+            (
+                r#"{"method":"foo","id":"a"}"#,
+                ErrorCode::InvalidRequest,
+                Some(Id::Str("a".into())),
+            ),
+            (
+                r#"{"jsonrpc":"2.0","id":1}"#,
+                ErrorCode::InvalidRequest,
+                Some(Id::Num(1)),
+            ),
+            (
+                r#"{"jsonrpc":"2.0","method":"foo","id":true}"#,
+                ErrorCode::InvalidRequest,
+                None,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","method":"foo","id":1,"unknown":[]}"#,
+                ErrorCode::InvalidRequest,
+                Some(Id::Num(1)),
+            ),
+            (
+                r#"{"jsonrpc":"2.0","method":"foo","params":1,"id":1}"#,
+                ErrorCode::InvalidRequest,
+                Some(Id::Num(1)),
+            ),
+            (r#"1"#, ErrorCode::InvalidRequest, None),
+        ];
+
+        for (case, code, id) in cases {
+            let err = Request::from_slice(case.as_bytes()).unwrap_err();
+            assert_eq!(err.error().code, code);
+            assert_eq!(err.id(), id.as_ref());
+        }
+    }
+
+    #[test]
+    fn decode_error_kind() {
+        let err = Request::from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":1"#).unwrap_err();
+        assert!(matches!(err, DecodeError::Parse(_)));
+        let err = Request::from_slice(br#"{"jsonrpc":"1.0","method":"foo","id":1}"#).unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidVersion { .. }));
+        let err = Request::from_slice(br#"{"jsonrpc":"2.0","params":[],"id":1}"#).unwrap_err();
+        assert!(matches!(err, DecodeError::MissingMethod { .. }));
+        let err = Request::from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":[]}"#).unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidId));
+        let err =
+            Request::from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":2,"bar":1}"#).unwrap_err();
+        match err {
+            DecodeError::UnknownField { field, id } => {
+                assert_eq!(field, "bar");
+                assert_eq!(id, Some(Id::Num(2)));
+            }
+            err => panic!("unexpected decode error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn decode_batch_error() {
+        // The id of the invalid call doesn't belong to the whole batch.
+        let err = Request::from_slice(br#"[{"jsonrpc":"2.0","method":"foo","id":1},{"jsonrpc":"2.0","method":"foo","id":2,"bar":1}]"#)
+            .unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidRequest { id: None, .. }));
+        let failure = err.into_failure();
+        assert_eq!(failure.error.code, ErrorCode::InvalidRequest);
+        assert_eq!(failure.id, Id::Null);
+
+        let err = Request::from_slice(b" [1, 2]").unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidRequest { id: None, .. }));
+    }
+
+    #[test]
+    fn decode_error_into_failure() {
+        let err = Request::from_slice(br#"{"jsonrpc":"2.0","id":1}"#).unwrap_err();
+        assert_eq!(
+            serde_json::to_string(&err.into_failure()).unwrap(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid request","data":"Missing field `method`"},"id":1}"#
+        );
+
+        let err = Request::from_slice(br#"{"jsonrpc":"2.0","method":"foo","id":1}]"#).unwrap_err();
+        let failure = Failure::from(err);
+        assert_eq!(failure.error.code, ErrorCode::ParseError);
+        assert_eq!(failure.id, Id::Null);
+    }
+}
@@ -0,0 +1,476 @@
+use std::fmt;
+
+use serde::{
+    de::{self, DeserializeOwned},
+    Serialize,
+};
+use serde_json::{value::RawValue, Value};
+
+use crate::{
+    raw,
+    v2::{
+        request::{Request, SubscriptionNotification},
+        response::Response,
+    },
+};
+
+/// Represents any JSON-RPC 2.0 message that a peer may receive on a bidirectional channel.
+///
+/// The kind of message is determined by the members of the object(s), rather than by trying
+/// to deserialize each kind in turn:
+///
+/// - an object with a `method` member is a request call, or a subscription notification if it
+///   has no `id` and its `params` is an object with exactly the `subscription` and `result`
+///   members;
+/// - an object with a `result` or an `error` member is a response output.
+///
+/// All the objects of a batch must be of the same kind.
+///
+/// ```rust
+/// use jsonrpc_types::{Message, Request, Response};
+///
+/// let message: Message = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"foo","id":1}"#).unwrap();
+/// assert!(matches!(message, Message::Request(Request::Single(_))));
+///
+/// let message: Message = serde_json::from_str(r#"[{"jsonrpc":"2.0","result":true,"id":1}]"#).unwrap();
+/// assert!(matches!(message, Message::Response(Response::Batch(_))));
+///
+/// let message: Message = serde_json::from_str(
+///     r#"{"jsonrpc":"2.0","method":"foo","params":{"subscription":1,"result":true}}"#
+/// ).unwrap();
+/// assert!(matches!(message, Message::SubscriptionNotification(_)));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Message<T = Value> {
+    /// Single or batch request
+    Request(Request),
+    /// Single or batch response
+    Response(Response<T>),
+    /// Single subscription notification
+    SubscriptionNotification(SubscriptionNotification<T>),
+    /// Batch of subscription notifications
+    SubscriptionNotifications(Vec<SubscriptionNotification<T>>),
+}
+
+impl<T: Serialize> fmt::Display for Message<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`Message` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<T> From<Request> for Message<T> {
+    fn from(request: Request) -> Self {
+        Self::Request(request)
+    }
+}
+
+impl<T> From<Response<T>> for Message<T> {
+    fn from(response: Response<T>) -> Self {
+        Self::Response(response)
+    }
+}
+
+impl<T> From<SubscriptionNotification<T>> for Message<T> {
+    fn from(notification: SubscriptionNotification<T>) -> Self {
+        Self::SubscriptionNotification(notification)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Call,
+    SubscriptionNotification,
+    Output,
+}
+
+/// The kind of a single message or of each message of a batch.
+enum Shape {
+    Single(Kind),
+    Batch(Vec<Kind>),
+}
+
+impl<'de> de::Deserialize<'de> for Shape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserializer::deserialize_any(deserializer, ShapeVisitor)
+    }
+}
+
+struct ShapeVisitor;
+impl<'de> de::Visitor<'de> for ShapeVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an object or a non-empty batch of objects")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut kinds = Vec::new();
+        while let Some(kind) = seq.next_element()? {
+            kinds.push(kind);
+        }
+        if kinds.is_empty() {
+            return Err(de::Error::invalid_length(0, &"a non-empty batch"));
+        }
+        Ok(Shape::Batch(kinds))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        KindVisitor.visit_map(map).map(Shape::Single)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Kind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserializer::deserialize_map(deserializer, KindVisitor)
+    }
+}
+
+/// Determines the kind of a message object from its members, ignoring their values.
+struct KindVisitor;
+impl<'de> de::Visitor<'de> for KindVisitor {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        use self::message_field::Field;
+
+        let (mut method, mut id, mut output, mut subscription) = (false, false, false, false);
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Params => {
+                    subscription = map.next_value::<SubscriptionParams>()?.0;
+                    continue;
+                }
+                Field::Method => method = true,
+                Field::Id => id = true,
+                Field::Result | Field::Error => output = true,
+                Field::Subscription | Field::Other => {}
+            }
+            map.next_value::<de::IgnoredAny>()?;
+        }
+
+        if method {
+            if !id && subscription {
+                Ok(Kind::SubscriptionNotification)
+            } else {
+                Ok(Kind::Call)
+            }
+        } else if output {
+            Ok(Kind::Output)
+        } else {
+            Err(de::Error::custom(
+                "expected a `method`, `result` or `error` member",
+            ))
+        }
+    }
+}
+
+/// Whether the `params` member is an object with exactly the `subscription` and `result`
+/// members, as the params of a subscription notification are.
+struct SubscriptionParams(bool);
+
+impl<'de> de::Deserialize<'de> for SubscriptionParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserializer::deserialize_any(deserializer, SubscriptionParamsVisitor)
+    }
+}
+
+struct SubscriptionParamsVisitor;
+impl<'de> de::Visitor<'de> for SubscriptionParamsVisitor {
+    type Value = SubscriptionParams;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+        Ok(SubscriptionParams(false))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        use self::message_field::Field;
+
+        let (mut subscription, mut result, mut other) = (0, 0, false);
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Subscription => subscription += 1,
+                Field::Result => result += 1,
+                _ => other = true,
+            }
+            map.next_value::<de::IgnoredAny>()?;
+        }
+        Ok(SubscriptionParams(
+            subscription == 1 && result == 1 && !other,
+        ))
+    }
+}
+
+mod message_field {
+    use super::*;
+
+    /// The members which determine the kind of a message; all the others are ignored.
+    pub enum Field {
+        Method,
+        Params,
+        Id,
+        Result,
+        Error,
+        Subscription,
+        Other,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "method" => Ok(Field::Method),
+                "params" => Ok(Field::Params),
+                "id" => Ok(Field::Id),
+                "result" => Ok(Field::Result),
+                "error" => Ok(Field::Error),
+                "subscription" => Ok(Field::Subscription),
+                _ => Ok(Field::Other),
+            }
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> de::Deserialize<'de> for Message<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // The kind is determined from the raw message, which is then deserialized only once.
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let (kind, batch) = match raw::decode(&raw)? {
+            Shape::Single(kind) => (kind, false),
+            Shape::Batch(kinds) => {
+                let mut kinds = kinds.into_iter();
+                let mut kind = kinds.next().expect("batch is non-empty");
+                for other in kinds {
+                    kind = match (kind, other) {
+                        (kind, other) if kind == other => kind,
+                        // Subscription notifications are notifications as well.
+                        (Kind::Call, Kind::SubscriptionNotification)
+                        | (Kind::SubscriptionNotification, Kind::Call) => Kind::Call,
+                        _ => {
+                            return Err(de::Error::custom("mixed requests and responses in batch"))
+                        }
+                    };
+                }
+                (kind, true)
+            }
+        };
+
+        match (kind, batch) {
+            (Kind::Call, _) => raw::decode(&raw).map(Message::Request),
+            (Kind::Output, _) => raw::decode(&raw).map(Message::Response),
+            (Kind::SubscriptionNotification, false) => {
+                raw::decode(&raw).map(Message::SubscriptionNotification)
+            }
+            (Kind::SubscriptionNotification, true) => {
+                raw::decode(&raw).map(Message::SubscriptionNotifications)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Map;
+
+    use super::*;
+    use crate::v2::{
+        Error, MethodCall, Notification, Output, Params, SubscriptionNotificationParams,
+    };
+
+    #[test]
+    fn message_serialization() {
+        let cases = vec![
+            (
+                Message::Request(Request::Single(
+                    MethodCall::new("foo", (1,), 1.into()).into(),
+                )),
+                r#"{"jsonrpc":"2.0","method":"foo","params":[1],"id":1}"#,
+            ),
+            (
+                Message::Request(Request::Batch(vec![
+                    Notification::new("foo", ()).into(),
+                    Notification::new("bar", Some(Params::Map(Map::new()))).into(),
+                ])),
+                r#"[{"jsonrpc":"2.0","method":"foo"},{"jsonrpc":"2.0","method":"bar","params":{}}]"#,
+            ),
+            (
+                Message::Response(Response::Single(Output::success(
+                    Value::Bool(true),
+                    1.into(),
+                ))),
+                r#"{"jsonrpc":"2.0","result":true,"id":1}"#,
+            ),
+            (
+                Message::Response(Response::Batch(vec![
+                    Output::success(Value::Bool(true), 1.into()),
+                    Output::failure(Error::method_not_found(), 2.into()),
+                ])),
+                r#"[{"jsonrpc":"2.0","result":true,"id":1},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}]"#,
+            ),
+            (
+                Message::SubscriptionNotification(SubscriptionNotification::new(
+                    "foo",
+                    SubscriptionNotificationParams::new(1.into(), Value::Bool(true)),
+                )),
+                r#"{"jsonrpc":"2.0","method":"foo","params":{"subscription":1,"result":true}}"#,
+            ),
+            (
+                Message::SubscriptionNotifications(vec![SubscriptionNotification::new(
+                    "foo",
+                    SubscriptionNotificationParams::new("a".into(), Value::Bool(true)),
+                )]),
+                r#"[{"jsonrpc":"2.0","method":"foo","params":{"subscription":"a","result":true}}]"#,
+            ),
+        ];
+
+        for (message, expect) in cases {
+            assert_eq!(message.to_string(), expect);
+            let decoded: Message = serde_json::from_str(expect).unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn message_discrimination() {
+        // A method call with a `subscription` param is still a request.
+        let message: Message = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","method":"foo","params":{"subscription":1,"result":true},"id":1}"#,
+        )
+        .unwrap();
+        assert!(matches!(message, Message::Request(Request::Single(_))));
+
+        // Subscription notifications batched with other calls are requests.
+        let message: Message = serde_json::from_str(
+            r#"[{"jsonrpc":"2.0","method":"foo","params":{"subscription":1,"result":true}},{"jsonrpc":"2.0","method":"bar","id":1}]"#,
+        )
+        .unwrap();
+        assert!(matches!(message, Message::Request(Request::Batch(_))));
+
+        // A notification whose params aren't the params of a subscription notification
+        // is a request.
+        let cases = vec![
+            r#"{"jsonrpc":"2.0","method":"unsubscribe","params":{"subscription":"abc"}}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":{"subscription":1,"result":true,"extra":1}}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1]}"#,
+        ];
+        for case in cases {
+            let message: Message = serde_json::from_str(case).unwrap();
+            assert!(
+                matches!(message, Message::Request(Request::Single(_))),
+                "{} should be a request",
+                case
+            );
+        }
+
+        // Errors keep their position in the message.
+        let err = serde_json::from_str::<Message>(r#"{"jsonrpc":"2.0","method":1,"id":1}"#)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "invalid type: integer `1`, expected a string at line 1 column 27"
+        );
+        let err = serde_json::from_str::<Message>(
+            r#"[{"jsonrpc":"2.0","method":"foo"},{"jsonrpc":"2.0","method":2}]"#,
+        )
+        .unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 61));
+        assert_eq!(err.to_string().matches(" at line ").count(), 1);
+
+        let invalid_cases = vec![
+            r#"[]"#,
+            r#"1"#,
+            r#"[1]"#,
+            r#"{"jsonrpc":"2.0","id":1}"#,
+            r#"[{"jsonrpc":"2.0","method":"foo","id":1},{"jsonrpc":"2.0","result":true,"id":1}]"#,
+            r#"{"jsonrpc":"2.0","result":true,"error":{"code":-32700,"message":"Parse error"},"id":1}"#,
+            r#"{"jsonrpc":"1.0","method":"foo","id":1}"#,
+        ];
+        for case in invalid_cases {
+            assert!(
+                serde_json::from_str::<Message>(case).is_err(),
+                "{} should be invalid",
+                case
+            );
+        }
+    }
+}
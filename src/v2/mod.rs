@@ -1,3 +1,11 @@
+/// Helpers for borrowed JSON-RPC 2.0 objects
+mod borrow;
+/// JSON-RPC 2.0 request decoding errors
+mod decode;
+/// JSON-RPC 2.0 messages of any kind
+mod message;
+/// JSON-RPC 2.0 request parameters conversion
+pub(crate) mod params;
 /// JSON-RPC 2.0 request objects
 mod request;
 /// JSON-RPC 2.0 response objects
@@ -6,17 +14,28 @@ mod response;
 mod version;
 
 pub use self::{
+    decode::DecodeError,
+    message::Message,
+    params::{FromParams, ToParams},
     request::{
-        Call, MethodCall, MethodCallRequest, Notification, Params, Request,
-        SubscriptionNotification, SubscriptionNotificationParams,
+        Call, CallRef, MethodCall, MethodCallRef, MethodCallRequest, Notification, NotificationRef,
+        Params, PartialRequest, Request, RequestRef, SubscriptionNotification,
+        SubscriptionNotificationParams,
+    },
+    response::{
+        Failure, Output, OutputRef, Response, ResponseRef, ResultError, Success, SuccessRef,
     },
-    response::{Failure, Output, Response, Success},
     version::Version,
 };
 pub use crate::{
-    error::{Error, ErrorCode},
+    error::{
+        CausePolicy, Error, ErrorCode, ErrorRegistry, ExposeCause, FromRpcError, IntoRpcError,
+        InvalidErrorCode, RedactCause,
+    },
     id::Id,
 };
+#[cfg(feature = "derive")]
+pub use jsonrpc_types_derive::{FromParams, FromRpcError, IntoRpcError, ToParams};
 
 // Re-exports
-pub use serde_json::{Map, Value};
+pub use serde_json::{value::RawValue, Map, Value};
@@ -0,0 +1,284 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{error::Error, v2::request::Params};
+
+/// Conversion into JSON-RPC 2.0 request parameters.
+///
+/// Tuples and `Vec`s are converted into parameters by-position, maps are converted into
+/// parameters by-name, and `()` omits the parameters.
+///
+/// With the `derive` feature enabled, `#[derive(ToParams)]` converts the fields of a struct
+/// into parameters by-name, or by-position if the struct is annotated with `#[params(positional)]`.
+///
+/// A conversion fails if a parameter value can't be serialized into JSON,
+/// e.g. a map whose keys aren't strings.
+pub trait ToParams: Sized {
+    /// Converts into request parameters, `None` means that the parameters are omitted.
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error>;
+
+    /// Converts into request parameters, `None` means that the parameters are omitted.
+    ///
+    /// This is a convenience for parameters which are known to be serializable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter value can't be serialized into JSON, see [`ToParams::try_to_params`].
+    fn to_params(self) -> Option<Params> {
+        self.try_to_params()
+            .expect("parameter values are serializable")
+    }
+}
+
+/// Conversion from JSON-RPC 2.0 request parameters.
+///
+/// With the `derive` feature enabled, `#[derive(FromParams)]` parses the fields of a struct
+/// from parameters either by-position or by-name, see [`Params::parse_named`].
+pub trait FromParams: Sized {
+    /// Parses request parameters, `None` means that the parameters were omitted.
+    fn from_params(params: Option<Params>) -> Result<Self, Error>;
+}
+
+impl ToParams for Params {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        Ok(Some(self))
+    }
+}
+
+impl FromParams for Params {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        Ok(params.unwrap_or_default())
+    }
+}
+
+impl ToParams for Option<Params> {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        Ok(self)
+    }
+}
+
+impl FromParams for Option<Params> {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        Ok(params)
+    }
+}
+
+impl ToParams for () {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        Ok(None)
+    }
+}
+
+impl FromParams for () {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        match params {
+            None => Ok(()),
+            Some(Params::Array(array)) if array.is_empty() => Ok(()),
+            Some(Params::Map(map)) if map.is_empty() => Ok(()),
+            Some(_) => Err(Error::invalid_params("expected no parameters")),
+        }
+    }
+}
+
+impl<T: Serialize> ToParams for Vec<T> {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        let array = self.into_iter().map(to_value).collect::<Result<_, _>>()?;
+        Ok(Some(Params::Array(array)))
+    }
+}
+
+impl<T: DeserializeOwned> FromParams for Vec<T> {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        params.unwrap_or_default().parse()
+    }
+}
+
+impl ToParams for Map<String, Value> {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        Ok(Some(Params::Map(self)))
+    }
+}
+
+impl FromParams for Map<String, Value> {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        into_map(params)
+    }
+}
+
+impl<V: Serialize> ToParams for BTreeMap<String, V> {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        let map = self
+            .into_iter()
+            .map(|(k, v)| Ok((k, to_value(v)?)))
+            .collect::<Result<_, _>>()?;
+        Ok(Some(Params::Map(map)))
+    }
+}
+
+impl<V: DeserializeOwned> FromParams for BTreeMap<String, V> {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        Params::Map(into_map(params)?).parse()
+    }
+}
+
+impl<V: Serialize, S: BuildHasher> ToParams for HashMap<String, V, S> {
+    fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+        let map = self
+            .into_iter()
+            .map(|(k, v)| Ok((k, to_value(v)?)))
+            .collect::<Result<_, _>>()?;
+        Ok(Some(Params::Map(map)))
+    }
+}
+
+impl<V: DeserializeOwned, S: BuildHasher + Default> FromParams for HashMap<String, V, S> {
+    fn from_params(params: Option<Params>) -> Result<Self, Error> {
+        Params::Map(into_map(params)?).parse()
+    }
+}
+
+macro_rules! tuple_params {
+    ($($len:expr => ($($name:ident)+))+) => {
+        $(
+            impl<$($name: Serialize),+> ToParams for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn try_to_params(self) -> Result<Option<Params>, serde_json::Error> {
+                    let ($($name,)+) = self;
+                    Ok(Some(Params::Array(vec![$(to_value($name)?),+])))
+                }
+            }
+
+            impl<$($name: DeserializeOwned),+> FromParams for ($($name,)+) {
+                fn from_params(params: Option<Params>) -> Result<Self, Error> {
+                    Params::Array(into_array(params, $len)?).parse()
+                }
+            }
+        )+
+    };
+}
+
+tuple_params! {
+    1 => (T0)
+    2 => (T0 T1)
+    3 => (T0 T1 T2)
+    4 => (T0 T1 T2 T3)
+    5 => (T0 T1 T2 T3 T4)
+    6 => (T0 T1 T2 T3 T4 T5)
+    7 => (T0 T1 T2 T3 T4 T5 T6)
+    8 => (T0 T1 T2 T3 T4 T5 T6 T7)
+    9 => (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    10 => (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    11 => (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    12 => (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+}
+
+/// Serializes a single parameter value.
+#[doc(hidden)]
+pub fn to_value<T: Serialize>(value: T) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(value)
+}
+
+/// Deserializes a single parameter value, a missing value is deserialized from Null.
+#[doc(hidden)]
+pub fn from_value<T: DeserializeOwned>(value: Option<Value>, name: &str) -> Result<T, Error> {
+    match value {
+        Some(value) => serde_json::from_value(value).map_err(|err| {
+            Error::invalid_params(format!("field `{}`: {}", name, err)).with_cause(err)
+        }),
+        None => serde_json::from_value(Value::Null).map_err(|err| {
+            Error::invalid_params(format!("missing field `{}`", name)).with_cause(err)
+        }),
+    }
+}
+
+/// Takes the parameters by-position, expecting at most `len` values.
+fn into_array(params: Option<Params>, len: usize) -> Result<Vec<Value>, Error> {
+    match params {
+        None => Ok(vec![]),
+        Some(Params::Array(array)) if array.len() > len => Err(Error::invalid_params(format!(
+            "invalid length {}, expected at most {} parameters",
+            array.len(),
+            len
+        ))),
+        Some(Params::Array(array)) => Ok(array),
+        Some(Params::Map(_)) => Err(Error::invalid_params("expected parameters by-position")),
+    }
+}
+
+/// Takes the parameters by-name.
+fn into_map(params: Option<Params>) -> Result<Map<String, Value>, Error> {
+    match params {
+        None => Ok(Map::new()),
+        Some(Params::Map(map)) => Ok(map),
+        Some(Params::Array(_)) => Err(Error::invalid_params("expected parameters by-name")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_params() {
+        assert_eq!(().to_params(), None);
+        assert_eq!(Option::<Params>::None.to_params(), None);
+        assert_eq!(
+            (1, "foo", true).to_params(),
+            Some(Params::Array(vec![
+                Value::from(1),
+                Value::from("foo"),
+                Value::Bool(true)
+            ]))
+        );
+        assert_eq!(
+            vec![1, 2].to_params(),
+            Some(Params::Array(vec![Value::from(1), Value::from(2)]))
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert("key".to_owned(), "value");
+        let mut expect = Map::new();
+        expect.insert("key".into(), Value::from("value"));
+        assert_eq!(map.to_params(), Some(Params::Map(expect)));
+
+        // A map whose keys aren't strings can't be serialized into JSON.
+        let mut map = BTreeMap::new();
+        map.insert((1, 2), "value");
+        assert!(vec![map.clone()].try_to_params().is_err());
+        assert!((1, map).try_to_params().is_err());
+    }
+
+    #[test]
+    fn from_params() {
+        let params = Some(Params::Array(vec![Value::from(1), Value::from("foo")]));
+        assert_eq!(
+            <(u64, String)>::from_params(params.clone()).unwrap(),
+            (1, "foo".to_owned())
+        );
+        assert_eq!(
+            <(u64,)>::from_params(params.clone()).unwrap_err(),
+            Error::invalid_params("invalid length 2, expected at most 1 parameters")
+        );
+        assert!(<()>::from_params(params).is_err());
+        assert!(<()>::from_params(None).is_ok());
+        assert!(Vec::<u64>::from_params(None).unwrap().is_empty());
+
+        let mut map = Map::new();
+        map.insert("key".into(), Value::from("value"));
+        let params = HashMap::<String, String>::from_params(Some(Params::Map(map))).unwrap();
+        assert_eq!(params["key"], "value");
+        assert_eq!(
+            HashMap::<String, String>::from_params(Some(Params::Array(vec![]))).unwrap_err(),
+            Error::invalid_params("expected parameters by-name")
+        );
+
+        let err = from_value::<u64>(None, "count").unwrap_err();
+        assert_eq!(err, Error::invalid_params("missing field `count`"));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
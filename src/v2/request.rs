@@ -1,15 +1,38 @@
-use std::fmt;
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+    fmt,
+    marker::PhantomData,
+    str::FromStr,
+};
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::{from_value, Map, Value};
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Serialize,
+};
+use serde_json::{
+    from_value,
+    value::{to_raw_value, RawValue},
+    Map, Value,
+};
 
-use crate::{error::Error, id::Id, v2::version::Version};
+use crate::{
+    error::Error,
+    id::Id,
+    v2::{
+        borrow::{check_raw_params, deserialize_raw_params, CowStr},
+        decode::DecodeError,
+        params::ToParams,
+        response::Failure,
+        version::Version,
+    },
+};
 
 /// Represents JSON-RPC 2.0 request parameters.
 ///
 /// If present, parameters for the rpc call MUST be provided as a Structured value.
 /// Either by-position through an Array or by-name through an Object.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Params {
     /// Array of values
@@ -31,6 +54,50 @@ impl fmt::Display for Params {
     }
 }
 
+impl<'de> de::Deserialize<'de> for Params {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de> {
+            marker: PhantomData<Params>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = Params;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an array or an object")
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let array = Vec::<Value>::deserialize(de::value::SeqAccessDeserializer::new(seq))?;
+                Ok(Params::Array(array))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let object =
+                    Map::<String, Value>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Params::Map(object))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<Params>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
 impl Params {
     /// Parses incoming `Params` into expected types.
     pub fn parse<D>(self) -> Result<D, Error>
@@ -38,7 +105,7 @@ impl Params {
         D: DeserializeOwned,
     {
         let value = self.into();
-        from_value(value).map_err(Error::invalid_params)
+        from_value(value).map_err(|err| Error::invalid_params(&err).with_cause(err))
     }
 
     /// Checks if the parameters is an empty array of objects.
@@ -55,6 +122,70 @@ impl Params {
     pub fn is_map(&self) -> bool {
         matches!(self, Params::Map(_))
     }
+
+    /// Parses incoming `Params` into expected types, accepting parameters both by-position
+    /// and by-name.
+    ///
+    /// `names` lists the fields of the expected type in positional order, an Array is mapped
+    /// onto them before parsing. Missing trailing positional parameters are left out of the map,
+    /// so that they can be parsed as optional fields.
+    pub fn parse_named<D>(self, names: &[&str]) -> Result<D, Error>
+    where
+        D: DeserializeOwned,
+    {
+        let map = self.into_named(names)?;
+        from_value(Value::Object(map)).map_err(|err| Error::invalid_params(&err).with_cause(err))
+    }
+
+    /// Converts the parameters into parameters by-name.
+    ///
+    /// An Array is mapped positionally onto `names`, a Map is returned as is.
+    /// Values which don't match any of `names` are rejected, as in `into_positional`.
+    pub fn into_named(self, names: &[&str]) -> Result<Map<String, Value>, Error> {
+        match self {
+            Params::Map(map) => match map.keys().find(|key| !names.contains(&key.as_str())) {
+                Some(key) => Err(Error::invalid_params(format!("unknown field `{}`", key))),
+                None => Ok(map),
+            },
+            Params::Array(array) if array.len() > names.len() => {
+                Err(Error::invalid_params(format!(
+                    "invalid length {}, expected at most {} parameters",
+                    array.len(),
+                    names.len()
+                )))
+            }
+            Params::Array(array) => Ok(names
+                .iter()
+                .map(|name| (*name).to_owned())
+                .zip(array)
+                .collect()),
+        }
+    }
+
+    /// Converts the parameters into parameters by-position.
+    ///
+    /// The values of a Map are ordered as `names`, missing values are filled with Null except for
+    /// the trailing ones, which are left out. An Array is returned as is.
+    pub fn into_positional(self, names: &[&str]) -> Result<Vec<Value>, Error> {
+        let mut map = match self {
+            Params::Array(array) => return Ok(array),
+            Params::Map(map) => map,
+        };
+        let mut array = names
+            .iter()
+            .map(|name| map.remove(*name))
+            .collect::<Vec<_>>();
+        if let Some(key) = map.keys().next() {
+            return Err(Error::invalid_params(format!("unknown field `{}`", key)));
+        }
+        while let Some(None) = array.last() {
+            array.pop();
+        }
+        Ok(array
+            .into_iter()
+            .map(|value| value.unwrap_or(Value::Null))
+            .collect())
+    }
 }
 
 impl From<Params> for Value {
@@ -95,13 +226,27 @@ impl fmt::Display for MethodCall {
 
 impl MethodCall {
     /// Creates a JSON-RPC 2.0 request which is a method call.
-    pub fn new<M: Into<String>>(method: M, params: Option<Params>, id: Id) -> Self {
-        Self {
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters can't be serialized into JSON, see [`MethodCall::try_new`].
+    pub fn new<M: Into<String>, P: ToParams>(method: M, params: P, id: Id) -> Self {
+        Self::try_new(method, params, id).expect("parameter values are serializable")
+    }
+
+    /// Creates a JSON-RPC 2.0 request which is a method call,
+    /// failing if the parameters can't be serialized into JSON.
+    pub fn try_new<M: Into<String>, P: ToParams>(
+        method: M,
+        params: P,
+        id: Id,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
             jsonrpc: Version::V2_0,
             method: method.into(),
-            params,
+            params: params.try_to_params()?,
             id,
-        }
+        })
     }
 }
 
@@ -137,12 +282,25 @@ impl fmt::Display for Notification {
 
 impl Notification {
     /// Creates a JSON-RPC 2.0 request which is a notification.
-    pub fn new<M: Into<String>>(method: M, params: Option<Params>) -> Self {
-        Self {
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters can't be serialized into JSON, see [`Notification::try_new`].
+    pub fn new<M: Into<String>, P: ToParams>(method: M, params: P) -> Self {
+        Self::try_new(method, params).expect("parameter values are serializable")
+    }
+
+    /// Creates a JSON-RPC 2.0 request which is a notification,
+    /// failing if the parameters can't be serialized into JSON.
+    pub fn try_new<M: Into<String>, P: ToParams>(
+        method: M,
+        params: P,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
             jsonrpc: Version::V2_0,
             method: method.into(),
-            params,
-        }
+            params: params.try_to_params()?,
+        })
     }
 }
 
@@ -197,8 +355,7 @@ impl<T: Serialize + DeserializeOwned> SubscriptionNotification<T> {
 }
 
 /// Represents single JSON-RPC 2.0 call.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Call {
     /// Call method
@@ -214,6 +371,93 @@ impl fmt::Display for Call {
     }
 }
 
+impl<'de> de::Deserialize<'de> for Call {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::request_field::{Field, FIELDS};
+
+        struct Visitor<'de> {
+            marker: PhantomData<Call>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = Call;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct Call")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut jsonrpc = Option::<Version>::None;
+                let mut method = Option::<String>::None;
+                let mut params = Option::<Option<Params>>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Jsonrpc => {
+                            if jsonrpc.is_some() {
+                                return Err(de::Error::duplicate_field("jsonrpc"));
+                            }
+                            jsonrpc = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Method => {
+                            if method.is_some() {
+                                return Err(de::Error::duplicate_field("method"));
+                            }
+                            method = Some(de::MapAccess::next_value::<String>(&mut map)?)
+                        }
+                        Field::Params => {
+                            if params.is_some() {
+                                return Err(de::Error::duplicate_field("params"));
+                            }
+                            params = Some(de::MapAccess::next_value::<Option<Params>>(&mut map)?)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let jsonrpc = jsonrpc.ok_or_else(|| de::Error::missing_field("jsonrpc"))?;
+                let method = method.ok_or_else(|| de::Error::missing_field("method"))?;
+                let params = params.flatten();
+                Ok(match id {
+                    Some(id) => Call::MethodCall(MethodCall {
+                        jsonrpc,
+                        method,
+                        params,
+                        id,
+                    }),
+                    None => Call::Notification(Notification {
+                        jsonrpc,
+                        method,
+                        params,
+                    }),
+                })
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "Call",
+            FIELDS,
+            Visitor {
+                marker: PhantomData::<Call>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
 impl Call {
     /// Returns the method of the request call.
     pub fn method(&self) -> &str {
@@ -253,8 +497,7 @@ impl From<Notification> for Call {
 }
 
 /// JSON-RPC 2.0 Request object.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Request {
     /// Single call
@@ -270,9 +513,120 @@ impl fmt::Display for Request {
     }
 }
 
+impl<'de> de::Deserialize<'de> for Request {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de> {
+            marker: PhantomData<Request>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = Request;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single call or a batch of calls")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let call = Call::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Request::Single(call))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut calls = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(call) = de::SeqAccess::next_element::<Call>(&mut seq)? {
+                    calls.push(call);
+                }
+                Ok(Request::Batch(calls))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<Request>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
+impl FromStr for Request {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_slice(s.as_bytes())
+    }
+}
+
+impl Request {
+    /// Decodes a JSON-RPC 2.0 request from bytes.
+    ///
+    /// Unlike `serde_json::from_slice`, the returned error tells malformed JSON apart
+    /// from invalid Request objects and can be turned into a failure response directly.
+    ///
+    /// An empty batch is rejected, as the specification requires it to be answered
+    /// with a single `InvalidRequest` failure.
+    pub fn from_slice(v: &[u8]) -> Result<Self, DecodeError> {
+        match serde_json::from_slice(v) {
+            Ok(Request::Batch(calls)) if calls.is_empty() => Err(DecodeError::EmptyBatch),
+            Ok(request) => Ok(request),
+            Err(err) => Err(DecodeError::classify(v, err)),
+        }
+    }
+
+    /// Decodes a JSON-RPC 2.0 request from bytes, decoding the calls of a batch one by one.
+    ///
+    /// An invalid call does not fail the whole batch, instead it is replaced by the failure
+    /// response it must be answered with, so that the valid calls can still be processed.
+    pub fn from_slice_partial(v: &[u8]) -> Result<PartialRequest, DecodeError> {
+        match Self::from_slice(v) {
+            Ok(Request::Single(call)) => Ok(PartialRequest::Single(call)),
+            Ok(Request::Batch(calls)) => {
+                Ok(PartialRequest::Batch(calls.into_iter().map(Ok).collect()))
+            }
+            Err(err @ DecodeError::Parse(_)) | Err(err @ DecodeError::EmptyBatch) => Err(err),
+            Err(err) => {
+                let calls = match serde_json::from_slice::<Vec<&RawValue>>(v) {
+                    Ok(calls) => calls,
+                    Err(_) => return Err(err),
+                };
+                let calls = calls
+                    .into_iter()
+                    .map(|call| {
+                        let call = call.get().as_bytes();
+                        serde_json::from_slice::<Call>(call)
+                            .map_err(|err| DecodeError::classify(call, err).into_failure())
+                    })
+                    .collect();
+                Ok(PartialRequest::Batch(calls))
+            }
+        }
+    }
+}
+
+/// JSON-RPC 2.0 Request object whose batch calls are decoded independently of each other.
+///
+/// See [`Request::from_slice_partial`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PartialRequest {
+    /// Single call
+    Single(Call),
+    /// Batch of calls, each of them being either a valid call or the failure response
+    /// that answers the invalid one
+    Batch(Vec<Result<Call, Failure>>),
+}
+
 /// JSON-RPC 2.0 Request object (only for method call).
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum MethodCallRequest {
     /// Single method call
@@ -281,6 +635,52 @@ pub enum MethodCallRequest {
     Batch(Vec<MethodCall>),
 }
 
+impl<'de> de::Deserialize<'de> for MethodCallRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de> {
+            marker: PhantomData<MethodCallRequest>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de> de::Visitor<'de> for Visitor<'de> {
+            type Value = MethodCallRequest;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single method call or a batch of method calls")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let call = MethodCall::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(MethodCallRequest::Single(call))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut calls = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(call) = de::SeqAccess::next_element::<MethodCall>(&mut seq)? {
+                    calls.push(call);
+                }
+                Ok(MethodCallRequest::Batch(calls))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<MethodCallRequest>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
 impl From<MethodCall> for MethodCallRequest {
     fn from(call: MethodCall) -> Self {
         Self::Single(call)
@@ -300,6 +700,462 @@ impl fmt::Display for MethodCallRequest {
     }
 }
 
+/// Represents borrowed JSON-RPC 2.0 request which is a method call.
+///
+/// Unlike [`MethodCall`], the method name and the raw JSON text of the parameters are borrowed
+/// from the input, so that the call can be routed before its parameters are parsed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MethodCallRef<'a> {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub jsonrpc: Version,
+    /// A String containing the name of the method to be invoked.
+    #[serde(borrow)]
+    pub method: Cow<'a, str>,
+    /// The raw JSON text of the parameters. This member MAY be omitted.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "deserialize_raw_params",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub params: Option<Cow<'a, RawValue>>,
+    /// An identifier established by the Client.
+    pub id: Id,
+}
+
+impl<'a> fmt::Display for MethodCallRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`MethodCallRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'a> MethodCallRef<'a> {
+    /// Creates a borrowed JSON-RPC 2.0 request which is a method call.
+    pub fn new(method: &'a str, params: Option<&'a RawValue>, id: Id) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: Cow::Borrowed(method),
+            params: params.map(Cow::Borrowed),
+            id,
+        }
+    }
+
+    /// Parses the raw parameters into expected types.
+    ///
+    /// Omitted parameters are parsed as an empty array.
+    pub fn parse_params<'b, D>(&'b self) -> Result<D, Error>
+    where
+        D: Deserialize<'b>,
+    {
+        parse_raw_params(&self.params)
+    }
+}
+
+impl<'a> From<&'a MethodCall> for MethodCallRef<'a> {
+    fn from(call: &'a MethodCall) -> Self {
+        Self {
+            jsonrpc: call.jsonrpc,
+            method: Cow::Borrowed(&call.method),
+            params: call.params.as_ref().map(params_to_raw),
+            id: call.id.clone(),
+        }
+    }
+}
+
+impl<'a> TryFrom<MethodCallRef<'a>> for MethodCall {
+    type Error = serde_json::Error;
+
+    fn try_from(call: MethodCallRef<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            jsonrpc: call.jsonrpc,
+            method: call.method.into_owned(),
+            params: raw_to_params(call.params)?,
+            id: call.id,
+        })
+    }
+}
+
+/// Represents borrowed JSON-RPC 2.0 request which is a notification.
+///
+/// Unlike [`Notification`], the method name and the raw JSON text of the parameters are borrowed
+/// from the input.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotificationRef<'a> {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub jsonrpc: Version,
+    /// A String containing the name of the method to be invoked.
+    #[serde(borrow)]
+    pub method: Cow<'a, str>,
+    /// The raw JSON text of the parameters. This member MAY be omitted.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "deserialize_raw_params",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub params: Option<Cow<'a, RawValue>>,
+}
+
+impl<'a> fmt::Display for NotificationRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`NotificationRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'a> NotificationRef<'a> {
+    /// Creates a borrowed JSON-RPC 2.0 request which is a notification.
+    pub fn new(method: &'a str, params: Option<&'a RawValue>) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            method: Cow::Borrowed(method),
+            params: params.map(Cow::Borrowed),
+        }
+    }
+
+    /// Parses the raw parameters into expected types.
+    ///
+    /// Omitted parameters are parsed as an empty array.
+    pub fn parse_params<'b, D>(&'b self) -> Result<D, Error>
+    where
+        D: Deserialize<'b>,
+    {
+        parse_raw_params(&self.params)
+    }
+}
+
+impl<'a> From<&'a Notification> for NotificationRef<'a> {
+    fn from(notification: &'a Notification) -> Self {
+        Self {
+            jsonrpc: notification.jsonrpc,
+            method: Cow::Borrowed(&notification.method),
+            params: notification.params.as_ref().map(params_to_raw),
+        }
+    }
+}
+
+impl<'a> TryFrom<NotificationRef<'a>> for Notification {
+    type Error = serde_json::Error;
+
+    fn try_from(notification: NotificationRef<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            jsonrpc: notification.jsonrpc,
+            method: notification.method.into_owned(),
+            params: raw_to_params(notification.params)?,
+        })
+    }
+}
+
+/// Represents single borrowed JSON-RPC 2.0 call.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum CallRef<'a> {
+    /// Call method
+    MethodCall(MethodCallRef<'a>),
+    /// Fire notification
+    Notification(NotificationRef<'a>),
+}
+
+impl<'a> fmt::Display for CallRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`CallRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for CallRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::request_field::{Field, FIELDS};
+
+        struct Visitor<'a> {
+            marker: PhantomData<CallRef<'a>>,
+        }
+        impl<'de: 'a, 'a> de::Visitor<'de> for Visitor<'a> {
+            type Value = CallRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct CallRef")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut jsonrpc = Option::<Version>::None;
+                let mut method = Option::<Cow<'a, str>>::None;
+                let mut params = Option::<Option<&'a RawValue>>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Jsonrpc => {
+                            if jsonrpc.is_some() {
+                                return Err(de::Error::duplicate_field("jsonrpc"));
+                            }
+                            jsonrpc = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Method => {
+                            if method.is_some() {
+                                return Err(de::Error::duplicate_field("method"));
+                            }
+                            method = Some(de::MapAccess::next_value::<CowStr<'a>>(&mut map)?.0)
+                        }
+                        Field::Params => {
+                            if params.is_some() {
+                                return Err(de::Error::duplicate_field("params"));
+                            }
+                            let raw = de::MapAccess::next_value::<Option<&'a RawValue>>(&mut map)?;
+                            check_raw_params(raw)?;
+                            params = Some(raw)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let jsonrpc = jsonrpc.ok_or_else(|| de::Error::missing_field("jsonrpc"))?;
+                let method = method.ok_or_else(|| de::Error::missing_field("method"))?;
+                let params = params.flatten().map(Cow::Borrowed);
+                Ok(match id {
+                    Some(id) => CallRef::MethodCall(MethodCallRef {
+                        jsonrpc,
+                        method,
+                        params,
+                        id,
+                    }),
+                    None => CallRef::Notification(NotificationRef {
+                        jsonrpc,
+                        method,
+                        params,
+                    }),
+                })
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "CallRef",
+            FIELDS,
+            Visitor {
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a> CallRef<'a> {
+    /// Returns the method of the request call.
+    pub fn method(&self) -> &str {
+        match self {
+            Self::MethodCall(call) => &call.method,
+            Self::Notification(notification) => &notification.method,
+        }
+    }
+
+    /// Returns the raw params of the request call.
+    pub fn params(&self) -> Option<&RawValue> {
+        match self {
+            Self::MethodCall(call) => call.params.as_deref(),
+            Self::Notification(notification) => notification.params.as_deref(),
+        }
+    }
+
+    /// Returns the id of the request call.
+    pub fn id(&self) -> Option<Id> {
+        match self {
+            Self::MethodCall(call) => Some(call.id.clone()),
+            Self::Notification(_notification) => None,
+        }
+    }
+}
+
+impl<'a> From<MethodCallRef<'a>> for CallRef<'a> {
+    fn from(call: MethodCallRef<'a>) -> Self {
+        Self::MethodCall(call)
+    }
+}
+
+impl<'a> From<NotificationRef<'a>> for CallRef<'a> {
+    fn from(notify: NotificationRef<'a>) -> Self {
+        Self::Notification(notify)
+    }
+}
+
+impl<'a> From<&'a Call> for CallRef<'a> {
+    fn from(call: &'a Call) -> Self {
+        match call {
+            Call::MethodCall(call) => Self::MethodCall(call.into()),
+            Call::Notification(notification) => Self::Notification(notification.into()),
+        }
+    }
+}
+
+impl<'a> TryFrom<CallRef<'a>> for Call {
+    type Error = serde_json::Error;
+
+    fn try_from(call: CallRef<'a>) -> Result<Self, Self::Error> {
+        Ok(match call {
+            CallRef::MethodCall(call) => Self::MethodCall(call.try_into()?),
+            CallRef::Notification(notification) => Self::Notification(notification.try_into()?),
+        })
+    }
+}
+
+/// Borrowed JSON-RPC 2.0 Request object.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum RequestRef<'a> {
+    /// Single call
+    Single(CallRef<'a>),
+    /// Batch of calls
+    Batch(Vec<CallRef<'a>>),
+}
+
+impl<'a> fmt::Display for RequestRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`RequestRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for RequestRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'a> {
+            marker: PhantomData<RequestRef<'a>>,
+        }
+        impl<'de: 'a, 'a> de::Visitor<'de> for Visitor<'a> {
+            type Value = RequestRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single call or a batch of calls")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let call = CallRef::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(RequestRef::Single(call))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut calls = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(call) = de::SeqAccess::next_element::<CallRef<'a>>(&mut seq)? {
+                    calls.push(call);
+                }
+                Ok(RequestRef::Batch(calls))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a> From<&'a Request> for RequestRef<'a> {
+    fn from(request: &'a Request) -> Self {
+        match request {
+            Request::Single(call) => Self::Single(call.into()),
+            Request::Batch(calls) => Self::Batch(calls.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl<'a> TryFrom<RequestRef<'a>> for Request {
+    type Error = serde_json::Error;
+
+    fn try_from(request: RequestRef<'a>) -> Result<Self, Self::Error> {
+        Ok(match request {
+            RequestRef::Single(call) => Self::Single(call.try_into()?),
+            RequestRef::Batch(calls) => Self::Batch(
+                calls
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+fn params_to_raw(params: &Params) -> Cow<'static, RawValue> {
+    Cow::Owned(to_raw_value(params).expect("`Params` is serializable"))
+}
+
+fn raw_to_params(raw: Option<Cow<'_, RawValue>>) -> Result<Option<Params>, serde_json::Error> {
+    raw.map(|raw| serde_json::from_str(raw.get())).transpose()
+}
+
+fn parse_raw_params<'b, D>(raw: &'b Option<Cow<'_, RawValue>>) -> Result<D, Error>
+where
+    D: Deserialize<'b>,
+{
+    let json = raw.as_deref().map_or("[]", RawValue::get);
+    serde_json::from_str(json).map_err(|err| Error::invalid_params(&err).with_cause(err))
+}
+
+pub(crate) mod request_field {
+    use super::*;
+
+    pub const FIELDS: &[&str] = &["jsonrpc", "method", "params", "id"];
+    pub enum Field {
+        Jsonrpc,
+        Method,
+        Params,
+        Id,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "jsonrpc" => Ok(Field::Jsonrpc),
+                "method" => Ok(Field::Method),
+                "params" => Ok(Field::Params),
+                "id" => Ok(Field::Id),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,6 +1224,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn params_parsed_by_name() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Foo {
+            name: String,
+            count: u64,
+            flag: Option<bool>,
+        }
+        let names = &["name", "count", "flag"];
+
+        let by_position = serde_json::from_str::<Params>(r#"["foo",1,true]"#).unwrap();
+        let by_name =
+            serde_json::from_str::<Params>(r#"{"name":"foo","count":1,"flag":true}"#).unwrap();
+        let expect = Foo {
+            name: "foo".into(),
+            count: 1,
+            flag: Some(true),
+        };
+        assert_eq!(by_position.parse_named::<Foo>(names).unwrap(), expect);
+        assert_eq!(by_name.parse_named::<Foo>(names).unwrap(), expect);
+
+        let trailing = serde_json::from_str::<Params>(r#"["foo",1]"#).unwrap();
+        assert_eq!(trailing.parse_named::<Foo>(names).unwrap().flag, None);
+
+        let missing = serde_json::from_str::<Params>(r#"["foo"]"#).unwrap();
+        let err = missing.parse_named::<Foo>(names).unwrap_err();
+        assert_eq!(err, Error::invalid_params("missing field `count`"));
+        // The serde error is kept as the cause.
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.is::<serde_json::Error>());
+        let too_many = serde_json::from_str::<Params>(r#"["foo",1,true,2]"#).unwrap();
+        assert_eq!(
+            too_many.parse_named::<Foo>(names).unwrap_err(),
+            Error::invalid_params("invalid length 4, expected at most 3 parameters")
+        );
+    }
+
+    #[test]
+    fn params_into_positional() {
+        let names = &["name", "count", "flag"];
+        let params = serde_json::from_str::<Params>(r#"{"flag":true,"name":"foo"}"#).unwrap();
+        assert_eq!(
+            params.into_positional(names).unwrap(),
+            vec![Value::from("foo"), Value::Null, Value::Bool(true)]
+        );
+        let params = serde_json::from_str::<Params>(r#"{"name":"foo"}"#).unwrap();
+        assert_eq!(
+            params.into_positional(names).unwrap(),
+            vec![Value::from("foo")]
+        );
+        let params = serde_json::from_str::<Params>(r#"{"name":"foo","other":1}"#).unwrap();
+        assert_eq!(
+            params.into_positional(names).unwrap_err(),
+            Error::invalid_params("unknown field `other`")
+        );
+    }
+
     fn method_call_cases() -> Vec<(MethodCall, &'static str)> {
         vec![
             (
@@ -455,6 +1368,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn unserializable_params() {
+        // A map whose keys aren't strings can't be serialized into JSON.
+        let mut map = std::collections::BTreeMap::new();
+        map.insert((1, 2), 3);
+        assert!(MethodCall::try_new("foo", vec![map.clone()], Id::Num(1)).is_err());
+        assert!(Notification::try_new("foo", (map,)).is_err());
+        assert!(Notification::try_new("foo", (1, 2)).is_ok());
+    }
+
     #[test]
     fn call_serialization() {
         for (method_call, expect) in method_call_cases() {
@@ -501,11 +1424,95 @@ mod tests {
             r#"[{"jsonrpc":"2.0","method":"foo","id":1},{"jsonrpc":"2.0","method":"bar","id":2}]"#;
         assert_eq!(serde_json::to_string(&batch_request).unwrap(), batch_expect);
         assert_eq!(
-            serde_json::from_str::<Request>(&batch_expect).unwrap(),
+            serde_json::from_str::<Request>(batch_expect).unwrap(),
             batch_request
         );
     }
 
+    #[test]
+    fn borrowed_request_serialization() {
+        for (method_call, expect) in method_call_cases() {
+            let call_ref = serde_json::from_str::<MethodCallRef>(expect).unwrap();
+            assert!(matches!(call_ref.method, Cow::Borrowed("foo")));
+            assert_eq!(serde_json::to_string(&call_ref).unwrap(), expect);
+            assert_eq!(MethodCall::try_from(call_ref).unwrap(), method_call);
+            let call_ref = MethodCallRef::from(&method_call);
+            assert_eq!(serde_json::to_string(&call_ref).unwrap(), expect);
+        }
+
+        for (notification, expect) in notification_cases() {
+            let notification_ref = serde_json::from_str::<NotificationRef>(expect).unwrap();
+            assert_eq!(serde_json::to_string(&notification_ref).unwrap(), expect);
+            assert_eq!(
+                Notification::try_from(notification_ref).unwrap(),
+                notification
+            );
+        }
+
+        let batch_request = Request::Batch(vec![
+            Call::MethodCall(MethodCall::new(
+                "foo",
+                Some(Params::Array(vec![Value::from(1)])),
+                1.into(),
+            )),
+            Call::Notification(Notification::new("bar", None)),
+        ]);
+        let batch_expect = r#"[{"jsonrpc":"2.0","method":"foo","params":[1],"id":1},{"jsonrpc":"2.0","method":"bar"}]"#;
+        let request_ref = serde_json::from_str::<RequestRef>(batch_expect).unwrap();
+        match &request_ref {
+            RequestRef::Batch(calls) => {
+                assert_eq!(calls[0].method(), "foo");
+                assert_eq!(calls[0].params().map(RawValue::get), Some("[1]"));
+                assert_eq!(calls[0].id(), Some(Id::Num(1)));
+                assert_eq!(calls[1].method(), "bar");
+                assert_eq!(calls[1].id(), None);
+            }
+            RequestRef::Single(_) => panic!("expected a batch request"),
+        }
+        assert_eq!(serde_json::to_string(&request_ref).unwrap(), batch_expect);
+        assert_eq!(Request::try_from(request_ref).unwrap(), batch_request);
+        assert_eq!(
+            serde_json::to_string(&RequestRef::from(&batch_request)).unwrap(),
+            batch_expect
+        );
+    }
+
+    #[test]
+    fn borrowed_params_parsed_as_tuple() {
+        let json = r#"{"jsonrpc":"2.0","method":"foo","params":["bar",1],"id":1}"#;
+        let call = serde_json::from_str::<MethodCallRef>(json).unwrap();
+        let params: (&str, u64) = call.parse_params().unwrap();
+        assert_eq!(params, ("bar", 1));
+
+        let call = MethodCallRef::new("foo", None, 1.into());
+        let params: Vec<u64> = call.parse_params().unwrap();
+        assert!(params.is_empty());
+
+        let call = serde_json::from_str::<MethodCallRef>(json).unwrap();
+        let err = call.parse_params::<(u64, u64)>().unwrap_err();
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn invalid_borrowed_request() {
+        let cases = vec![
+            // JSON-RPC 2.0 invalid request
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":1,"unknown":[]}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":1.2}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":1,"id":1}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":"bar"}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","unknown":[]}"#,
+            r#"{"jsonrpc":"1.0","method":"foo"}"#,
+            r#"{"method":"foo"}"#,
+            r#"{"jsonrpc":"2.0","unknown":[]}"#,
+        ];
+
+        for case in cases {
+            let request = serde_json::from_str::<RequestRef>(case);
+            assert!(request.is_err());
+        }
+    }
+
     #[test]
     fn invalid_request() {
         let cases = vec![
@@ -513,7 +1520,7 @@ mod tests {
             r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":1,"unknown":[]}"#,
             r#"{"jsonrpc":"2.0"`,"method":"foo","params":[1,true],"id":1.2}"#,
             r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":null,"unknown":[]}"#,
-            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":null}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":true}"#,
             r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"unknown":[]}"#,
             r#"{"jsonrpc":"2.0","method":"foo","unknown":[]}"#,
             r#"{"jsonrpc":"2.0","unknown":[]}"#,
@@ -525,11 +1532,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn invalid_request_error_message() {
+        let err = serde_json::from_str::<Request>(
+            r#"{"jsonrpc":"2.0","method":"foo","id":1,"unknown":[]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("unknown field `unknown`"));
+
+        let err = serde_json::from_str::<Request>(r#"[{"jsonrpc":"2.0","id":1}]"#).unwrap_err();
+        assert!(err.to_string().starts_with("missing field `method`"));
+
+        let err =
+            serde_json::from_str::<Request>(r#"{"jsonrpc":"2.0","method":"foo","method":"bar"}"#)
+                .unwrap_err();
+        assert!(err.to_string().starts_with("duplicate field `method`"));
+
+        let err = serde_json::from_str::<Params>("1").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid type: integer `1`, expected an array or an object"));
+    }
+
+    #[test]
+    fn partial_batch_request() {
+        let err = Request::from_slice_partial(b"[]").unwrap_err();
+        assert!(matches!(err, DecodeError::EmptyBatch));
+        assert_eq!(
+            serde_json::to_string(&err.into_failure()).unwrap(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid request","data":"Empty batch"},"id":null}"#
+        );
+
+        let err = Request::from_slice_partial(
+            br#"[{"jsonrpc":"2.0","method":"sum","params":[1,2,4],"id":"1"},{"jsonrpc":"2.0","method"]"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, DecodeError::Parse(_)));
+
+        let request = Request::from_slice_partial(b"[1,2]").unwrap();
+        match request {
+            PartialRequest::Batch(calls) => {
+                assert_eq!(calls.len(), 2);
+                for call in calls {
+                    let failure = call.unwrap_err();
+                    assert_eq!(failure.error.code, Error::invalid_request().code);
+                    assert_eq!(failure.id, Id::Null);
+                }
+            }
+            PartialRequest::Single(_) => panic!("expected a batch request"),
+        }
+
+        let request = Request::from_slice_partial(
+            br#"[
+                {"jsonrpc":"2.0","method":"sum","params":[1,2,4],"id":"1"},
+                {"jsonrpc":"2.0","method":"notify_hello","params":[7]},
+                {"foo":"boo"},
+                {"jsonrpc":"2.0","params":[],"id":"2"}
+            ]"#,
+        )
+        .unwrap();
+        match request {
+            PartialRequest::Batch(calls) => {
+                assert_eq!(calls.len(), 4);
+                assert_eq!(calls[0].as_ref().unwrap().method(), "sum");
+                assert_eq!(calls[1].as_ref().unwrap().method(), "notify_hello");
+                assert_eq!(calls[2].as_ref().unwrap_err().id, Id::Null);
+                assert_eq!(calls[3].as_ref().unwrap_err().id, Id::Str("2".into()));
+            }
+            PartialRequest::Single(_) => panic!("expected a batch request"),
+        }
+
+        let request = Request::from_slice_partial(br#"{"jsonrpc":"2.0","method":"foo"}"#).unwrap();
+        assert_eq!(
+            request,
+            PartialRequest::Single(Call::Notification(Notification::new("foo", None)))
+        );
+        let err = Request::from_slice_partial(br#"{"jsonrpc":"2.0","id":1}"#).unwrap_err();
+        assert!(matches!(err, DecodeError::MissingMethod { .. }));
+    }
+
     #[test]
     fn valid_request() {
         let cases = vec![
             // JSON-RPC 2.0 valid request
             r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":1}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":-1}"#,
+            r#"{"jsonrpc":"2.0","method":"foo","params":[1,true],"id":null}"#,
             r#"{"jsonrpc":"2.0","method":"foo","params":[],"id":1}"#,
             r#"{"jsonrpc":"2.0","method":"foo","id":1}"#,
             r#"{"jsonrpc":"2.0","method":"foo","params":[1,true]}"#,
@@ -1,12 +1,23 @@
-use std::fmt;
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+    fmt,
+    marker::PhantomData,
+};
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Serialize,
+};
+use serde_json::{
+    value::{to_raw_value, RawValue},
+    Value,
+};
 
 use crate::{
-    error::{Error, ErrorCode},
+    error::{Error, ErrorCode, FromRpcError},
     id::Id,
-    v2::version::Version,
+    v2::{borrow::deserialize_raw, version::Version},
 };
 
 /// Represents JSON-RPC 2.0 success response.
@@ -44,30 +55,30 @@ impl<T: Serialize + DeserializeOwned> Success<T> {
 /// Represents JSON-RPC 2.0 failure response.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct Failure {
+pub struct Failure<D = Value> {
     /// A String specifying the version of the JSON-RPC protocol.
     pub jsonrpc: Version,
     /// Failed execution error.
-    pub error: Error,
+    pub error: Error<D>,
     /// Correlation id.
     ///
     /// It **MUST** be the same as the value of the id member in the Request Object.
     ///
     /// If there was an error in detecting the id in the Request object (e.g. Parse error/Invalid Request),
     /// it **MUST** be Null.
-    pub id: Option<Id>,
+    pub id: Id,
 }
 
-impl fmt::Display for Failure {
+impl<D: Serialize> fmt::Display for Failure<D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let json = serde_json::to_string(self).expect("`Failure` is serializable");
         write!(f, "{}", json)
     }
 }
 
-impl Failure {
+impl<D> Failure<D> {
     /// Creates a JSON-RPC 2.0 failure response.
-    pub fn new(error: Error, id: Option<Id>) -> Self {
+    pub fn new(error: Error<D>, id: Id) -> Self {
         Self {
             jsonrpc: Version::V2_0,
             error,
@@ -77,38 +88,126 @@ impl Failure {
 }
 
 /// Represents success / failure output of JSON-RPC 2.0 response.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
-pub enum Output<T = Value> {
+pub enum Output<T = Value, D = Value> {
     /// Success response output
     Success(Success<T>),
     /// Failure response output
-    Failure(Failure),
+    Failure(Failure<D>),
 }
 
-impl<T: Serialize> fmt::Display for Output<T> {
+impl<T: Serialize, D: Serialize> fmt::Display for Output<T, D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let json = serde_json::to_string(self).expect("`Output` is serializable");
         write!(f, "{}", json)
     }
 }
 
+impl<'de, T: Deserialize<'de>, E: Deserialize<'de>> de::Deserialize<'de> for Output<T, E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::response_field::{Field, FIELDS};
+
+        struct Visitor<'de, T, E> {
+            marker: PhantomData<Output<T, E>>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de, T: Deserialize<'de>, E: Deserialize<'de>> de::Visitor<'de> for Visitor<'de, T, E> {
+            type Value = Output<T, E>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct Output")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut jsonrpc = Option::<Version>::None;
+                let mut result = Option::<T>::None;
+                let mut error = Option::<Error<E>>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Jsonrpc => {
+                            if jsonrpc.is_some() {
+                                return Err(de::Error::duplicate_field("jsonrpc"));
+                            }
+                            jsonrpc = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Result => {
+                            if result.is_some() {
+                                return Err(de::Error::duplicate_field("result"));
+                            }
+                            result = Some(de::MapAccess::next_value::<T>(&mut map)?)
+                        }
+                        Field::Error => {
+                            if error.is_some() {
+                                return Err(de::Error::duplicate_field("error"));
+                            }
+                            error = Some(de::MapAccess::next_value::<Error<E>>(&mut map)?)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let jsonrpc = jsonrpc.ok_or_else(|| de::Error::missing_field("jsonrpc"))?;
+                match (result, error, id) {
+                    (Some(result), None, Some(id)) => Ok(Output::Success(Success {
+                        jsonrpc,
+                        result,
+                        id,
+                    })),
+                    (Some(_), None, None) => Err(de::Error::missing_field("id")),
+                    // The id of a failure may be omitted, e.g. by servers which couldn't detect it.
+                    (None, Some(error), id) => Ok(Output::Failure(Failure {
+                        jsonrpc,
+                        error,
+                        id: id.unwrap_or_default(),
+                    })),
+                    _ => Err(de::Error::custom("Invalid JSON-RPC 2.0 response")),
+                }
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "Output",
+            FIELDS,
+            Visitor {
+                marker: PhantomData::<Output<T, E>>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
 impl<T: Serialize + DeserializeOwned> Output<T> {
     /// Creates a JSON-RPC 2.0 success response output.
     pub fn success(result: T, id: Id) -> Self {
         Self::Success(Success::new(result, id))
     }
 
-    /// Creates a JSON-RPC 2.0 failure response output.
-    pub fn failure(error: Error, id: Option<Id>) -> Self {
-        Self::Failure(Failure::new(error, id))
-    }
-
     /// Creates a new failure output indicating malformed request.
-    pub fn invalid_request(id: Option<Id>) -> Self {
+    pub fn invalid_request(id: Id) -> Self {
         Self::Failure(Failure::new(Error::new(ErrorCode::InvalidRequest), id))
     }
+}
+
+impl<T: Serialize + DeserializeOwned, D> Output<T, D> {
+    /// Creates a JSON-RPC 2.0 failure response output.
+    pub fn failure(error: Error<D>, id: Id) -> Self {
+        Self::Failure(Failure::new(error, id))
+    }
 
     /// Gets the JSON-RPC protocol version.
     pub fn version(&self) -> Version {
@@ -119,28 +218,77 @@ impl<T: Serialize + DeserializeOwned> Output<T> {
     }
 
     /// Gets the correlation id.
-    pub fn id(&self) -> Option<Id> {
+    pub fn id(&self) -> Id {
         match self {
-            Self::Success(s) => Some(s.id.clone()),
+            Self::Success(s) => s.id.clone(),
             Self::Failure(f) => f.id.clone(),
         }
     }
 }
 
-impl<T: Serialize + DeserializeOwned> From<Output<T>> for Result<T, Error> {
+impl Output {
+    /// Converts into a result, deserializing the raw result into `U`.
+    ///
+    /// This allows each output of a heterogeneous batch response to be decoded with its own type.
+    pub fn into_result<U: DeserializeOwned>(self) -> Result<U, ResultError> {
+        match self {
+            Self::Success(s) => serde_json::from_value(s.result).map_err(ResultError::Decode),
+            Self::Failure(f) => Err(ResultError::Rpc(f.error)),
+        }
+    }
+}
+
+impl<T, D, E> From<Output<T, D>> for Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+    E: FromRpcError<D>,
+{
     // Convert into a result.
-    // Will be `Ok` if it is a `SuccessResponse` and `Err` if `FailureResponse`.
-    fn from(output: Output<T>) -> Result<T, Error> {
+    // Will be `Ok` if it is a `SuccessResponse` and `Err` if `FailureResponse`,
+    // with the error converted into `E`.
+    fn from(output: Output<T, D>) -> Result<T, E> {
         match output {
             Output::Success(s) => Ok(s.result),
-            Output::Failure(f) => Err(f.error),
+            Output::Failure(f) => Err(E::from_rpc_error(f.error)),
+        }
+    }
+}
+
+/// The error of converting an output into the result of a method call.
+#[derive(Debug)]
+pub enum ResultError {
+    /// The Server replied with a failure.
+    Rpc(Error),
+    /// The Server replied with a success, but the result could not be deserialized.
+    Decode(serde_json::Error),
+}
+
+impl fmt::Display for ResultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc(err) => write!(f, "JSON-RPC error: {}", err),
+            Self::Decode(err) => write!(f, "Invalid result: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ResultError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Rpc(err) => Some(err),
+            Self::Decode(err) => Some(err),
         }
     }
 }
 
+impl From<Error> for ResultError {
+    fn from(err: Error) -> Self {
+        Self::Rpc(err)
+    }
+}
+
 /// JSON-RPC 2.0 Response object.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Response<T = Value> {
     /// Single response
@@ -156,6 +304,52 @@ impl<T: Serialize> fmt::Display for Response<T> {
     }
 }
 
+impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for Response<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'de, T> {
+            marker: PhantomData<Response<T>>,
+            lifetime: PhantomData<&'de ()>,
+        }
+        impl<'de, T: Deserialize<'de>> de::Visitor<'de> for Visitor<'de, T> {
+            type Value = Response<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single output or a batch of outputs")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let output = Output::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Response::Single(output))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut outputs = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(output) = de::SeqAccess::next_element::<Output<T>>(&mut seq)? {
+                    outputs.push(output);
+                }
+                Ok(Response::Batch(outputs))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData::<Response<T>>,
+                lifetime: PhantomData,
+            },
+        )
+    }
+}
+
 impl<T> From<Success<T>> for Response<T> {
     fn from(success: Success<T>) -> Self {
         Response::Single(Output::<T>::Success(success))
@@ -168,6 +362,360 @@ impl<T> From<Failure> for Response<T> {
     }
 }
 
+/// Represents borrowed JSON-RPC 2.0 success response.
+///
+/// Unlike [`Success`], the raw JSON text of the result is borrowed from the input,
+/// so that the response can be correlated before its result is parsed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuccessRef<'a> {
+    /// A String specifying the version of the JSON-RPC protocol.
+    pub jsonrpc: Version,
+    /// The raw JSON text of the successful execution result.
+    #[serde(borrow, deserialize_with = "deserialize_raw")]
+    pub result: Cow<'a, RawValue>,
+    /// Correlation id.
+    pub id: Id,
+}
+
+impl<'a> fmt::Display for SuccessRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`SuccessRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'a> SuccessRef<'a> {
+    /// Creates a borrowed JSON-RPC 2.0 success response.
+    pub fn new(result: &'a RawValue, id: Id) -> Self {
+        Self {
+            jsonrpc: Version::V2_0,
+            result: Cow::Borrowed(result),
+            id,
+        }
+    }
+
+    /// Parses the raw result into expected types.
+    pub fn parse_result<'b, T>(&'b self) -> Result<T, serde_json::Error>
+    where
+        T: Deserialize<'b>,
+    {
+        serde_json::from_str(self.result.get())
+    }
+}
+
+impl<'a, T: Serialize> TryFrom<&'a Success<T>> for SuccessRef<'a> {
+    type Error = serde_json::Error;
+
+    fn try_from(success: &'a Success<T>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            jsonrpc: success.jsonrpc,
+            result: Cow::Owned(to_raw_value(&success.result)?),
+            id: success.id.clone(),
+        })
+    }
+}
+
+impl<'a, T: DeserializeOwned> TryFrom<SuccessRef<'a>> for Success<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(success: SuccessRef<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            jsonrpc: success.jsonrpc,
+            result: serde_json::from_str(success.result.get())?,
+            id: success.id,
+        })
+    }
+}
+
+/// Represents success / failure output of borrowed JSON-RPC 2.0 response.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum OutputRef<'a> {
+    /// Success response output
+    Success(SuccessRef<'a>),
+    /// Failure response output
+    Failure(Failure),
+}
+
+impl<'a> fmt::Display for OutputRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`OutputRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for OutputRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use self::response_field::{Field, FIELDS};
+
+        struct Visitor<'a> {
+            marker: PhantomData<OutputRef<'a>>,
+        }
+        impl<'de: 'a, 'a> de::Visitor<'de> for Visitor<'a> {
+            type Value = OutputRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("struct OutputRef")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut jsonrpc = Option::<Version>::None;
+                let mut result = Option::<&'a RawValue>::None;
+                let mut error = Option::<Error>::None;
+                let mut id = Option::<Id>::None;
+
+                while let Some(key) = de::MapAccess::next_key::<Field>(&mut map)? {
+                    match key {
+                        Field::Jsonrpc => {
+                            if jsonrpc.is_some() {
+                                return Err(de::Error::duplicate_field("jsonrpc"));
+                            }
+                            jsonrpc = Some(de::MapAccess::next_value::<Version>(&mut map)?)
+                        }
+                        Field::Result => {
+                            if result.is_some() {
+                                return Err(de::Error::duplicate_field("result"));
+                            }
+                            result = Some(de::MapAccess::next_value::<&'a RawValue>(&mut map)?)
+                        }
+                        Field::Error => {
+                            if error.is_some() {
+                                return Err(de::Error::duplicate_field("error"));
+                            }
+                            error = Some(de::MapAccess::next_value::<Error>(&mut map)?)
+                        }
+                        Field::Id => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(de::MapAccess::next_value::<Id>(&mut map)?)
+                        }
+                    }
+                }
+
+                let jsonrpc = jsonrpc.ok_or_else(|| de::Error::missing_field("jsonrpc"))?;
+                match (result, error, id) {
+                    (Some(result), None, Some(id)) => Ok(OutputRef::Success(SuccessRef {
+                        jsonrpc,
+                        result: Cow::Borrowed(result),
+                        id,
+                    })),
+                    (Some(_), None, None) => Err(de::Error::missing_field("id")),
+                    (None, Some(error), id) => Ok(OutputRef::Failure(Failure {
+                        jsonrpc,
+                        error,
+                        id: id.unwrap_or_default(),
+                    })),
+                    _ => Err(de::Error::custom("Invalid JSON-RPC 2.0 response")),
+                }
+            }
+        }
+
+        de::Deserializer::deserialize_struct(
+            deserializer,
+            "OutputRef",
+            FIELDS,
+            Visitor {
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a> OutputRef<'a> {
+    /// Gets the JSON-RPC protocol version.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::Success(s) => s.jsonrpc,
+            Self::Failure(f) => f.jsonrpc,
+        }
+    }
+
+    /// Gets the correlation id.
+    pub fn id(&self) -> Id {
+        match self {
+            Self::Success(s) => s.id.clone(),
+            Self::Failure(f) => f.id.clone(),
+        }
+    }
+
+    /// Converts into a result, parsing the raw result into expected types.
+    pub fn parse_result<'b, U>(&'b self) -> Result<U, ResultError>
+    where
+        U: Deserialize<'b>,
+    {
+        match self {
+            Self::Success(s) => s.parse_result().map_err(ResultError::Decode),
+            Self::Failure(f) => Err(ResultError::Rpc(f.error.clone())),
+        }
+    }
+}
+
+impl<'a, T: Serialize> TryFrom<&'a Output<T>> for OutputRef<'a> {
+    type Error = serde_json::Error;
+
+    fn try_from(output: &'a Output<T>) -> Result<Self, Self::Error> {
+        Ok(match output {
+            Output::Success(s) => Self::Success(s.try_into()?),
+            Output::Failure(f) => Self::Failure(f.clone()),
+        })
+    }
+}
+
+impl<'a, T: DeserializeOwned> TryFrom<OutputRef<'a>> for Output<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(output: OutputRef<'a>) -> Result<Self, Self::Error> {
+        Ok(match output {
+            OutputRef::Success(s) => Self::Success(s.try_into()?),
+            OutputRef::Failure(f) => Self::Failure(f),
+        })
+    }
+}
+
+/// Borrowed JSON-RPC 2.0 Response object.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum ResponseRef<'a> {
+    /// Single response
+    Single(OutputRef<'a>),
+    /// Response to batch request (batch of responses)
+    Batch(Vec<OutputRef<'a>>),
+}
+
+impl<'a> fmt::Display for ResponseRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).expect("`ResponseRef` is serializable");
+        write!(f, "{}", json)
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for ResponseRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<'a> {
+            marker: PhantomData<ResponseRef<'a>>,
+        }
+        impl<'de: 'a, 'a> de::Visitor<'de> for Visitor<'a> {
+            type Value = ResponseRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a single output or a batch of outputs")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let output = OutputRef::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(ResponseRef::Single(output))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut outputs = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(output) = de::SeqAccess::next_element::<OutputRef<'a>>(&mut seq)? {
+                    outputs.push(output);
+                }
+                Ok(ResponseRef::Batch(outputs))
+            }
+        }
+
+        de::Deserializer::deserialize_any(
+            deserializer,
+            Visitor {
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T: Serialize> TryFrom<&'a Response<T>> for ResponseRef<'a> {
+    type Error = serde_json::Error;
+
+    fn try_from(response: &'a Response<T>) -> Result<Self, Self::Error> {
+        Ok(match response {
+            Response::Single(output) => Self::Single(output.try_into()?),
+            Response::Batch(outputs) => Self::Batch(
+                outputs
+                    .iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl<'a, T: DeserializeOwned> TryFrom<ResponseRef<'a>> for Response<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(response: ResponseRef<'a>) -> Result<Self, Self::Error> {
+        Ok(match response {
+            ResponseRef::Single(output) => Self::Single(output.try_into()?),
+            ResponseRef::Batch(outputs) => Self::Batch(
+                outputs
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+mod response_field {
+    use super::*;
+
+    pub const FIELDS: &[&str] = &["jsonrpc", "result", "error", "id"];
+    pub enum Field {
+        Jsonrpc,
+        Result,
+        Error,
+        Id,
+    }
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            de::Deserializer::deserialize_identifier(deserializer, FieldVisitor)
+        }
+    }
+
+    struct FieldVisitor;
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v {
+                "jsonrpc" => Ok(Field::Jsonrpc),
+                "result" => Ok(Field::Result),
+                "error" => Ok(Field::Error),
+                "id" => Ok(Field::Id),
+                _ => Err(de::Error::unknown_field(v, FIELDS)),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,7 +739,7 @@ mod tests {
                 Failure {
                     jsonrpc: Version::V2_0,
                     error: Error::parse_error(),
-                    id: Some(Id::Num(1)),
+                    id: Id::Num(1),
                 },
                 r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":1}"#,
             ),
@@ -200,7 +748,7 @@ mod tests {
                 Failure {
                     jsonrpc: Version::V2_0,
                     error: Error::parse_error(),
-                    id: None,
+                    id: Id::Null,
                 },
                 r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#,
             ),
@@ -289,6 +837,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn borrowed_response_serialization() {
+        for (success_resp, expect) in success_response_cases() {
+            let success_ref = serde_json::from_str::<SuccessRef>(expect).unwrap();
+            assert_eq!(success_ref.result.get(), "true");
+            assert!(success_ref.parse_result::<bool>().unwrap());
+            assert_eq!(serde_json::to_string(&success_ref).unwrap(), expect);
+            assert_eq!(Success::try_from(success_ref).unwrap(), success_resp);
+            let success_ref = SuccessRef::try_from(&success_resp).unwrap();
+            assert_eq!(serde_json::to_string(&success_ref).unwrap(), expect);
+        }
+
+        for (failure_resp, expect) in failure_response_cases() {
+            let output_ref = serde_json::from_str::<OutputRef>(expect).unwrap();
+            assert_eq!(output_ref.id(), failure_resp.id);
+            assert_eq!(serde_json::to_string(&output_ref).unwrap(), expect);
+            assert_eq!(
+                Output::<Value>::try_from(output_ref).unwrap(),
+                Output::Failure(failure_resp)
+            );
+        }
+
+        for ((success_resp, success_expect), (failure_resp, failure_expect)) in
+            success_response_cases()
+                .into_iter()
+                .zip(failure_response_cases())
+        {
+            let batch_response = Response::Batch(vec![
+                Output::Success(success_resp),
+                Output::Failure(failure_resp),
+            ]);
+            let batch_expect = format!("[{},{}]", success_expect, failure_expect);
+            let response_ref = serde_json::from_str::<ResponseRef>(&batch_expect).unwrap();
+            assert_eq!(serde_json::to_string(&response_ref).unwrap(), batch_expect);
+            assert_eq!(Response::try_from(response_ref).unwrap(), batch_response);
+            assert_eq!(
+                serde_json::to_string(&ResponseRef::try_from(&batch_response).unwrap()).unwrap(),
+                batch_expect
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_borrowed_response() {
+        let cases = vec![
+            // JSON-RPC 2.0 invalid response
+            r#"{"jsonrpc":"2.0","result":true,"id":1,"unknown":[]}"#,
+            r#"{"jsonrpc":"2.0","result":true,"id":1.2}"#,
+            r#"{"jsonrpc":"2.0","result":true,"error":{"code": -32700,"message": "Parse error"},"id":1}"#,
+            r#"{"jsonrpc":"2.0","id":1}"#,
+            r#"{"result":true,"id":1}"#,
+            r#"{"jsonrpc":"2.0","result":true}"#,
+            r#"{"jsonrpc":"2.0","unknown":[]}"#,
+        ];
+
+        for case in cases {
+            let response = serde_json::from_str::<ResponseRef>(case);
+            assert!(response.is_err());
+        }
+    }
+
     #[test]
     fn invalid_response() {
         let cases = vec![
@@ -297,6 +906,7 @@ mod tests {
             r#"{"jsonrpc":"2.0","error":{"code": -32700,"message": "Parse error"},"id":1,"unknown":[]}"#,
             r#"{"jsonrpc":"2.0","result":true,"error":{"code": -32700,"message": "Parse error"},"id":1}"#,
             r#"{"jsonrpc":"2.0","id":1}"#,
+            r#"{"jsonrpc":"2.0","result":true}"#,
             r#"{"jsonrpc":"2.0","unknown":[]}"#,
         ];
 
@@ -311,6 +921,7 @@ mod tests {
         let cases = vec![
             // JSON-RPC 2.0 valid response
             r#"{"jsonrpc":"2.0","result":true,"id":1}"#,
+            r#"{"jsonrpc":"2.0","result":true,"id":null}"#,
             r#"{"jsonrpc":"2.0","error":{"code": -32700,"message": "Parse error"},"id":1}"#,
             r#"{"jsonrpc":"2.0","error":{"code": -32700,"message": "Parse error"},"id":null}"#,
         ];
@@ -320,4 +931,95 @@ mod tests {
             assert!(response.is_ok());
         }
     }
+
+    #[test]
+    fn failure_without_id() {
+        let json = r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"}}"#;
+        let expect = Output::<Value>::failure(Error::parse_error(), Id::Null);
+        assert_eq!(serde_json::from_str::<Output>(json).unwrap(), expect);
+        let output = serde_json::from_str::<OutputRef>(json).unwrap();
+        assert_eq!(output.id(), Id::Null);
+        // The omitted id is serialized as null.
+        assert_eq!(
+            expect.to_string(),
+            r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#
+        );
+    }
+
+    #[test]
+    fn heterogeneous_batch_results() {
+        let response = serde_json::from_str::<Response>(
+            r#"[{"jsonrpc":"2.0","result":"foo","id":1},{"jsonrpc":"2.0","result":[1,2],"id":2},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":3}]"#,
+        )
+        .unwrap();
+        let outputs = match response {
+            Response::Batch(outputs) => outputs,
+            Response::Single(_) => unreachable!(),
+        };
+        let mut outputs = outputs.into_iter();
+        assert_eq!(
+            outputs.next().unwrap().into_result::<String>().unwrap(),
+            "foo"
+        );
+        let output = outputs.next().unwrap();
+        assert_eq!(
+            output.clone().into_result::<Vec<u64>>().unwrap(),
+            vec![1, 2]
+        );
+        assert!(matches!(
+            output.into_result::<String>(),
+            Err(ResultError::Decode(_))
+        ));
+        match outputs.next().unwrap().into_result::<String>() {
+            Err(ResultError::Rpc(err)) => assert_eq!(err, Error::method_not_found()),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let response = serde_json::from_str::<ResponseRef>(
+            r#"[{"jsonrpc":"2.0","result":"foo","id":1},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}]"#,
+        )
+        .unwrap();
+        let outputs = match response {
+            ResponseRef::Batch(outputs) => outputs,
+            ResponseRef::Single(_) => unreachable!(),
+        };
+        assert_eq!(outputs[0].parse_result::<&str>().unwrap(), "foo");
+        assert!(matches!(
+            outputs[0].parse_result::<u64>(),
+            Err(ResultError::Decode(_))
+        ));
+        assert!(matches!(
+            outputs[1].parse_result::<&str>(),
+            Err(ResultError::Rpc(_))
+        ));
+    }
+
+    #[test]
+    fn typed_error_data() {
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+        struct Details {
+            field: String,
+        }
+
+        let expect = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params","data":{"field":"foo"}},"id":1}"#;
+        let error = Error::with_data(
+            ErrorCode::InvalidParams,
+            "Invalid params",
+            Some(Details {
+                field: "foo".into(),
+            }),
+        );
+        let output = Output::<u64, Details>::failure(error.clone(), 1.into());
+        assert_eq!(output.to_string(), expect);
+        assert_eq!(
+            serde_json::from_str::<Output<u64, Details>>(expect).unwrap(),
+            output
+        );
+
+        let output = serde_json::from_str::<Output>(expect).unwrap();
+        match output {
+            Output::Failure(f) => assert_eq!(f.error.try_data_into::<Details>().unwrap(), error),
+            Output::Success(_) => unreachable!(),
+        }
+    }
 }